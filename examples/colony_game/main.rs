@@ -3,6 +3,7 @@ pub mod game_types;
 use game_types::*;
 use gridava::core::collection::Collection;
 
+use gridava::hex::algorithms::longest_trail;
 use gridava::hex::edge::Edge;
 use gridava::hex::vertex::Vertex;
 use gridava::{axial, hex::coordinate::Axial, hex::shape::HexShape};
@@ -61,6 +62,15 @@ fn main() {
         // Otherwise collect resources with the roll result
         collect_resources(roll, &game_board, &mut player_array[0]);
     }
+
+    // Work out who holds the longest road.
+    for player in player_array.iter() {
+        println!(
+            "Player {} longest road: {}",
+            player.id,
+            longest_road(&game_board, player)
+        );
+    }
 }
 
 pub fn generate_island() -> HexShape<GameTile> {
@@ -184,14 +194,15 @@ pub fn place_house(
         board
             .verts
             .get(vert)
-            .map_or(false, |val| val.vert_type != DevType::None)
+            .is_some_and(|val| val.vert_type != DevType::None)
     });
 
     // Check if we have a road on one of our adjacent edges.
     let is_on_road = vert.adjacent_edges().iter().any(|edge| {
-        board.edges.get(edge).map_or(false, |val| {
-            val.edge_type == EdgeType::Road && val.owning_player == player_id
-        })
+        board
+            .edges
+            .get(edge)
+            .is_some_and(|val| val.edge_type == EdgeType::Road && val.owning_player == player_id)
     });
 
     // Both values must be false in order to be a valid placement.
@@ -228,16 +239,18 @@ pub fn place_road(
 
     // Is there a road adjacent to this road?
     let is_road_adjacent = edge.adjacent_edges().iter().any(|e| {
-        board.edges.get(e).map_or(false, |val| {
-            val.edge_type == EdgeType::Road && val.owning_player == player_id
-        })
+        board
+            .edges
+            .get(e)
+            .is_some_and(|val| val.edge_type == EdgeType::Road && val.owning_player == player_id)
     });
 
     // Do we have a owned and developed vertex adjacent to us?
     let is_development_adjacent = edge.endpoints().iter().any(|v| {
-        board.verts.get(v).map_or(false, |val| {
-            val.vert_type != DevType::None && val.owning_player == player_id
-        })
+        board
+            .verts
+            .get(v)
+            .is_some_and(|val| val.vert_type != DevType::None && val.owning_player == player_id)
     });
 
     // Either value must be false to be a valid placement
@@ -255,3 +268,26 @@ pub fn place_road(
         );
     })
 }
+
+/// Calculate the longest road of a player.
+///
+/// A road is broken by a development owned by another player, the road may still end on that development.
+pub fn longest_road(board: &GameBoard, player: &Player) -> usize {
+    let player_id = player.id;
+
+    let (length, _) = longest_trail(
+        board.edges.keys().copied(),
+        |edge| {
+            board.edges.get(edge).is_some_and(|val| {
+                val.edge_type == EdgeType::Road && val.owning_player == player_id
+            })
+        },
+        |vert| {
+            board
+                .verts
+                .get(vert)
+                .is_some_and(|val| val.vert_type != DevType::None && val.owning_player != player_id)
+        },
+    );
+    length
+}
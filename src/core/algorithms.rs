@@ -122,7 +122,10 @@ where
 
 #[cfg(all(test, any(feature = "std", feature = "alloc")))]
 mod tests {
+    #[cfg(not(feature = "std"))]
     use alloc::format;
+    #[cfg(feature = "std")]
+    use std::format;
 
     use super::*;
 
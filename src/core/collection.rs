@@ -6,3 +6,11 @@ pub trait Collection<C, T> {
     /// The ability to set a coordinate in the schema, this can be thought of like assignment, or HashMap insert function.
     fn set(&mut self, coord: C, data: T);
 }
+
+/// The collection trait that defines how data is read back from a storage schema.
+///
+/// Used when copying data out of a collection, such as extracting a region into a shape.
+pub trait ReadCollection<C, T> {
+    /// The ability to get the data at a coordinate in the schema, this can be thought of like a HashMap get function.
+    fn get(&self, coord: &C) -> Option<&T>;
+}
@@ -60,7 +60,9 @@ impl<T: Neg<Output = T>> Neg for Vector2D<T> {
 
 /// Transformation matrix data structure.
 ///
-/// Stores translation, rotation and scale data to be able to perform operations with.
+/// Stores translation, rotation, scale and reflection data to be able to perform operations with.
+///
+/// The order of applications is reflection, rotation then translation.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
 pub struct Transform<T: Copy + AddAssign> {
@@ -70,15 +72,21 @@ pub struct Transform<T: Copy + AddAssign> {
     pub rotation: i32,
     /// 2D scale of an object.
     pub scale: Vector2D<f32>, // Can this be a coordinate or even a tuple of floats and not a i32?
+    /// Whether the object is mirrored before it is rotated.
+    ///
+    /// The axis of the mirror is defined by the grid, hexagonal grids mirror across the q axis.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reflected: bool,
 }
 
 /// Helper macro to create [`Transform`].
 ///
-/// Accepts 1, 2, or 3 arguments
+/// Accepts 1, 2, 3 or 4 arguments
 ///
 /// - 1 => translation.
 /// - 2 => translation, rotation.
 /// - 3 => translation, rotation, scale.
+/// - 4 => translation, rotation, scale, reflected.
 #[macro_export]
 macro_rules! transform {
     ($t:expr) => {
@@ -86,6 +94,7 @@ macro_rules! transform {
             translation: $t,
             rotation: 0,
             scale: vector2d!(1.0),
+            reflected: false,
         }
     };
 
@@ -94,6 +103,7 @@ macro_rules! transform {
             translation: $t,
             rotation: $r,
             scale: vector2d!(1.0, 1.0),
+            reflected: false,
         }
     };
 
@@ -102,6 +112,16 @@ macro_rules! transform {
             translation: $t,
             rotation: $r,
             scale: $s,
+            reflected: false,
+        }
+    };
+
+    ($t:expr, $r:expr, $s:expr, $f:expr) => {
+        Transform {
+            translation: $t,
+            rotation: $r,
+            scale: $s,
+            reflected: $f,
         }
     };
 }
@@ -121,10 +141,15 @@ impl<T: Copy + AddAssign + Default> Default for Transform<T> {
             translation: Default::default(),
             rotation: Default::default(),
             scale: vector2d!(1.0),
+            reflected: false,
         }
     }
 }
 
+/// Sum each component of two transforms.
+///
+/// This is not the same as applying one transform after the other, hex transforms have
+/// [`Transform::compose`] for that in [`crate::hex::coordinate`].
 impl<T> Add<Transform<T>> for Transform<T>
 where
     T: Copy + AddAssign + Add<T, Output = T>,
@@ -136,6 +161,7 @@ where
             translation: self.translation + rhs.translation,
             rotation: self.rotation + rhs.rotation,
             scale: self.scale + rhs.scale,
+            reflected: self.reflected ^ rhs.reflected,
         }
     }
 }
@@ -151,6 +177,7 @@ where
             translation: self.translation * -1,
             rotation: -self.rotation,
             scale: -self.scale,
+            reflected: self.reflected,
         }
     }
 }
@@ -188,6 +215,10 @@ mod tests {
         assert_eq!(Transform::default(), transform!(0, 0, vector2d!(1.0, 1.0)));
         assert_eq!(transform!(2), transform!(2, 0, vector2d!(1.0, 1.0)));
         assert_eq!(transform!(2, 4), transform!(2, 4, vector2d!(1.0, 1.0)));
+        assert_eq!(
+            transform!(2, 4, vector2d!(1.0, 1.0)),
+            transform!(2, 4, vector2d!(1.0, 1.0), false)
+        );
     }
 
     #[test]
@@ -198,6 +229,10 @@ mod tests {
             trans.add(transform!(1, 2, vector2d!(0.5, 1.5))),
             transform!(1, 2, vector2d!(1.5, 2.5))
         );
+
+        let mirrored = transform!(0, 0, vector2d!(1.0, 1.0), true);
+        assert!(mirrored.add(trans).reflected);
+        assert!(!mirrored.add(mirrored).reflected);
     }
 
     #[test]
@@ -0,0 +1,753 @@
+//! Algorithms specific to hexagonal grids.
+
+use crate::lib::*;
+
+use super::{coordinate::Axial, edge::Edge, vertex::Vertex};
+
+/// Working state for [`longest_trail`].
+struct TrailSearch {
+    /// Every edge in the network.
+    edges: Vec<Edge>,
+    /// Indices into `edges` of the edges touching a vertex.
+    incidence: BTreeMap<Vertex, Vec<usize>>,
+    /// Vertices a trail may end on but never pass through.
+    blocked: BTreeSet<Vertex>,
+    /// Whether an edge is already part of the current trail.
+    used: Vec<bool>,
+    /// The trail currently being explored.
+    path: Vec<usize>,
+    /// The longest trail found so far.
+    best: Vec<usize>,
+}
+
+impl TrailSearch {
+    /// The vertices a longest trail may start on.
+    ///
+    /// A longest trail can not be extended, so its ends are vertices with an odd number of edges or blocking
+    /// vertices. The only exception is a loop, which then covers a whole connected part of the network that
+    /// has neither, and can be started anywhere along it.
+    fn starts(&self) -> Vec<Vertex> {
+        let mut starts = vec![];
+        let mut seen = BTreeSet::new();
+
+        for vert in self.incidence.keys() {
+            if !seen.insert(*vert) {
+                continue;
+            }
+
+            // Walk the connected part of the network holding this vertex.
+            let mut part = vec![*vert];
+            let mut open = vec![*vert];
+            while let Some(current) = open.pop() {
+                for idx in &self.incidence[&current] {
+                    for next in self.edges[*idx].endpoints() {
+                        if seen.insert(next) {
+                            part.push(next);
+                            open.push(next);
+                        }
+                    }
+                }
+            }
+
+            let len = starts.len();
+            starts.extend(
+                part.iter().copied().filter(|vert| {
+                    self.incidence[vert].len() % 2 == 1 || self.blocked.contains(vert)
+                }),
+            );
+            if starts.len() == len {
+                starts.push(*vert);
+            }
+        }
+
+        starts
+    }
+
+    /// Extend the current trail from `vert` as far as it can go, recording the longest result.
+    fn extend(&mut self, vert: Vertex) {
+        if self.path.len() > self.best.len() {
+            self.best.clone_from(&self.path);
+        }
+
+        // A trail that uses every edge cannot be beaten, stop searching.
+        if self.best.len() == self.edges.len() {
+            return;
+        }
+
+        // A trail may start on a blocking vertex but it can not pass through one.
+        if !self.path.is_empty() && self.blocked.contains(&vert) {
+            return;
+        }
+
+        let incident = match self.incidence.get(&vert) {
+            Some(incident) => incident.clone(),
+            None => return,
+        };
+
+        for idx in incident {
+            if self.used[idx] {
+                continue;
+            }
+
+            let [a, b] = self.edges[idx].endpoints();
+            let next = if a == vert { b } else { a };
+
+            self.used[idx] = true;
+            self.path.push(idx);
+            self.extend(next);
+            self.path.pop();
+            self.used[idx] = false;
+        }
+    }
+}
+
+/// Find the longest trail through a network of edges.
+///
+/// A trail is a walk that never uses the same edge twice, it may however revisit a vertex. This is the
+/// rule used for calculating the 'longest road' in colony style board games.
+///
+/// `edges` are the candidate edges and `is_owned` selects which of those form the network. `is_blocking`
+/// marks vertices that interrupt a trail, such as another player's settlement. A trail may begin or end
+/// on a blocking vertex but can never pass through one.
+///
+/// Returns the length of the trail along with the edges that form it in walking order.
+///
+/// # Example
+/// ```
+/// use gridava::hex::algorithms::longest_trail;
+/// use gridava::hex::edge::{Edge, EdgeDirection, edge};
+///
+/// let roads = [
+///     edge!(0, 0, EdgeDirection::NorthWest),
+///     edge!(0, 0, EdgeDirection::NorthEast),
+///     edge!(1, 0, EdgeDirection::NorthWest),
+/// ];
+///
+/// let (length, trail) = longest_trail(roads, |_| true, |_| false);
+/// assert_eq!(length, 3);
+/// ```
+///
+/// The search is exact. It only starts from vertices a longest trail can end on, those with one or three
+/// edges and blocking vertices, or a single vertex of a part of the network that is one closed loop. Each
+/// vertex touches at most three edges so a trail can branch at most two ways at each step, and the search
+/// ends early once a trail using every edge is found. This keeps networks the size of a board game's road
+/// network fast to search.
+///
+/// The worst case is still exponential, up to `2^n` trails from each start for a network of `n` edges, so
+/// large densely connected networks, like every edge of a big region, should not be searched.
+pub fn longest_trail<I, E, V>(edges: I, mut is_owned: E, mut is_blocking: V) -> (usize, Vec<Edge>)
+where
+    I: IntoIterator<Item = Edge>,
+    E: FnMut(&Edge) -> bool,
+    V: FnMut(&Vertex) -> bool,
+{
+    // Deduplicate and order the network so results are deterministic.
+    let edges: Vec<Edge> = edges
+        .into_iter()
+        .filter(|edge| is_owned(edge))
+        .collect::<BTreeSet<Edge>>()
+        .into_iter()
+        .collect();
+
+    let mut incidence: BTreeMap<Vertex, Vec<usize>> = BTreeMap::new();
+    for (idx, edge) in edges.iter().enumerate() {
+        for vert in edge.endpoints() {
+            incidence.entry(vert).or_default().push(idx);
+        }
+    }
+
+    let blocked = incidence
+        .keys()
+        .filter(|vert| is_blocking(vert))
+        .copied()
+        .collect();
+
+    let mut search = TrailSearch {
+        used: vec![false; edges.len()],
+        edges,
+        incidence,
+        blocked,
+        path: vec![],
+        best: vec![],
+    };
+
+    for start in search.starts() {
+        search.extend(start);
+    }
+
+    let trail: Vec<Edge> = search.best.iter().map(|idx| search.edges[*idx]).collect();
+    (trail.len(), trail)
+}
+
+/// Find every coordinate reachable within a number of steps.
+///
+/// `neighbors` is the movement model, it returns the coordinates that can be moved to in a single step.
+/// Use [`Axial::neighbors`] for open ground or [`Axial::movement_neighbors`] to respect blocked edges.
+///
+/// The result includes `start` and is sorted. The search stops once nothing new can be reached, so a large
+/// `range` over an enclosed area is cheap.
+///
+/// # Example
+/// ```
+/// use gridava::hex::algorithms::movement_range;
+/// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+///
+/// let wall = axial!(0, 0).edge(HexDirection::Front);
+///
+/// // Same as axial!(0, 0).range(2) but walking around the wall.
+/// let coords = movement_range(axial!(0, 0), 2, |coord| coord.movement_neighbors(|edge| *edge == wall));
+/// ```
+pub fn movement_range<N, I>(start: Axial, range: i32, mut neighbors: N) -> Vec<Axial>
+where
+    N: FnMut(&Axial) -> I,
+    I: IntoIterator<Item = Axial>,
+{
+    let mut visited = BTreeSet::from([start]);
+    let mut frontier = vec![start];
+
+    // Expand one ring of movement at a time.
+    for _ in 0..range {
+        if frontier.is_empty() {
+            break;
+        }
+
+        let mut next = vec![];
+        for coord in frontier {
+            for neighbor in neighbors(&coord) {
+                if visited.insert(neighbor) {
+                    next.push(neighbor);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    visited.into_iter().collect()
+}
+
+/// Check if there is an unobstructed line between two coordinates.
+///
+/// Walks [`Axial::line`] from `a` to `b` and checks that every step is allowed by the `neighbors` movement
+/// model. Use [`Axial::movement_neighbors`] so sight is blocked by walls on edges.
+///
+/// # Example
+/// ```
+/// use gridava::hex::algorithms::line_of_sight;
+/// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+///
+/// let wall = axial!(1, 0).edge(HexDirection::Front);
+///
+/// // The wall is between (1, 0) and (2, 0)
+/// assert!(!line_of_sight(axial!(0, 0), axial!(3, 0), |coord| coord.movement_neighbors(|edge| *edge == wall)));
+/// ```
+pub fn line_of_sight<N, I>(a: Axial, b: Axial, mut neighbors: N) -> bool
+where
+    N: FnMut(&Axial) -> I,
+    I: IntoIterator<Item = Axial>,
+{
+    if a == b {
+        return true;
+    }
+
+    a.line(b).windows(2).all(|pair| {
+        neighbors(&pair[0])
+            .into_iter()
+            .any(|coord| coord == pair[1])
+    })
+}
+
+/// Find a shortest path between two coordinates.
+///
+/// `neighbors` is the movement model, see [`movement_range`]. The search will not look further than
+/// `max_steps` from `start`, this bounds the search on an unbounded grid when `goal` can not be reached.
+///
+/// Returns the path including `start` and `goal`, or None if there is no path within `max_steps`.
+///
+/// # Example
+/// ```
+/// use gridava::hex::algorithms::find_path;
+/// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+///
+/// let wall = axial!(0, 0).edge(HexDirection::Front);
+///
+/// // Walks around the wall, (0, 0) -> (1, -1) -> (1, 0)
+/// let path = find_path(axial!(0, 0), axial!(1, 0), 10, |coord| coord.movement_neighbors(|edge| *edge == wall));
+/// ```
+pub fn find_path<N, I>(
+    start: Axial,
+    goal: Axial,
+    max_steps: i32,
+    mut neighbors: N,
+) -> Option<Vec<Axial>>
+where
+    N: FnMut(&Axial) -> I,
+    I: IntoIterator<Item = Axial>,
+{
+    // Every coordinate found so far and the coordinate it was reached from.
+    let mut came_from = BTreeMap::from([(start, start)]);
+    let mut frontier = vec![start];
+    let mut steps = 0;
+
+    while !came_from.contains_key(&goal) {
+        if frontier.is_empty() || steps == max_steps {
+            return None;
+        }
+
+        let mut next = vec![];
+        for coord in frontier {
+            for neighbor in neighbors(&coord) {
+                if let btree_map::Entry::Vacant(entry) = came_from.entry(neighbor) {
+                    entry.insert(coord);
+                    next.push(neighbor);
+                }
+            }
+        }
+        frontier = next;
+        steps += 1;
+    }
+
+    // Walk back from the goal to build the path.
+    let mut path = vec![goal];
+    let mut current = goal;
+    while current != start {
+        current = came_from[&current];
+        path.push(current);
+    }
+    path.reverse();
+
+    Some(path)
+}
+
+/// The boundary of a region of coordinates.
+///
+/// See [`outline`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Outline {
+    /// Coordinates in the region with at least one neighbor outside of it, sorted.
+    pub hexes: Vec<Axial>,
+    /// Edges between the region and the outside, sorted.
+    pub edges: Vec<Edge>,
+    /// Closed loops of vertices around the outside of each connected part of the region.
+    ///
+    /// The first vertex is not repeated at the end of a loop.
+    pub outer: Vec<Vec<Vertex>>,
+    /// Closed loops of vertices around the holes inside the region.
+    ///
+    /// Holes are walked in the opposite direction to the outer loops.
+    pub holes: Vec<Vec<Vertex>>,
+}
+
+/// Find the boundary of a region of coordinates.
+///
+/// Outer loops are walked clockwise (pointy-top, r pointing down) and holes counter clockwise, so the region is
+/// always on the right of the walk. Each loop starts at its least vertex and loops are sorted, making the result
+/// deterministic.
+///
+/// # Example
+/// ```
+/// use gridava::hex::algorithms::outline;
+/// use gridava::hex::coordinate::{Axial, axial};
+///
+/// // A ring of tiles around (0, 0).
+/// let ring = axial!(0, 0).neighbors();
+/// let border = outline(ring);
+///
+/// assert_eq!(border.outer.len(), 1);
+/// assert_eq!(border.holes.len(), 1);
+/// ```
+pub fn outline<I>(coords: I) -> Outline
+where
+    I: IntoIterator<Item = Axial>,
+{
+    let region: BTreeSet<Axial> = coords.into_iter().collect();
+
+    // Every boundary edge, directed so the region is on its right.
+    let mut directed = BTreeMap::new();
+    let mut hexes = vec![];
+    for coord in &region {
+        let vertices = coord.vertices();
+        let mut on_boundary = false;
+        for (idx, edge) in coord.edges().into_iter().enumerate() {
+            let [a, b] = edge.adjacent_hexes();
+            let other = if a == *coord { b } else { a };
+            if !region.contains(&other) {
+                directed.insert(edge, (vertices[idx], vertices[(idx + 1) % 6]));
+                on_boundary = true;
+            }
+        }
+        if on_boundary {
+            hexes.push(*coord);
+        }
+    }
+
+    let edges: Vec<Edge> = directed.keys().copied().collect();
+
+    // A vertex touches three hexes so the boundary passes through it at most once, following the other
+    // boundary edge at each vertex always closes a loop.
+    let mut outer = vec![];
+    let mut holes = vec![];
+    let mut visited = BTreeSet::new();
+    for edge in &edges {
+        if !visited.insert(*edge) {
+            continue;
+        }
+
+        let (start, mut current) = directed[edge];
+        let mut lp = vec![start];
+        let mut last = *edge;
+        while current != start {
+            lp.push(current);
+            last = current
+                .adjacent_edges()
+                .into_iter()
+                .find(|next| *next != last && directed.contains_key(next))
+                .unwrap();
+            visited.insert(last);
+            let [a, b] = last.endpoints();
+            current = if a == current { b } else { a };
+        }
+
+        // Shoelace area, positive when walking clockwise with r pointing down.
+        let area: i64 = (0..lp.len())
+            .map(|idx| {
+                let (ax, ay) = lp[idx].scaled_position();
+                let (bx, by) = lp[(idx + 1) % lp.len()].scaled_position();
+                ax * by - bx * ay
+            })
+            .sum();
+
+        // Loops are built from the least edge so start from the least vertex instead.
+        let least = (0..lp.len()).min_by_key(|idx| lp[*idx]).unwrap();
+        lp.rotate_left(least);
+
+        match area > 0 {
+            true => outer.push(lp),
+            false => holes.push(lp),
+        }
+    }
+
+    outer.sort();
+    holes.sort();
+
+    Outline {
+        hexes,
+        edges,
+        outer,
+        holes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        axial,
+        hex::{
+            coordinate::HexDirection,
+            edge::{edge, EdgeDirection},
+            vertex::{vertex, VertexSpin},
+        },
+    };
+
+    // Check that consecutive edges share an endpoint and that no edge repeats.
+    fn is_trail(trail: &[Edge]) -> bool {
+        let unique: BTreeSet<&Edge> = trail.iter().collect();
+        unique.len() == trail.len()
+            && trail.windows(2).all(|pair| {
+                pair[0]
+                    .endpoints()
+                    .iter()
+                    .any(|vert| pair[1].endpoints().contains(vert))
+            })
+    }
+
+    #[test]
+    fn longest_trail() {
+        // Empty network.
+        assert_eq!(super::longest_trail([], |_| true, |_| false), (0, vec![]));
+
+        // Nothing owned.
+        assert_eq!(
+            super::longest_trail(axial!(0, 0).edges(), |_| false, |_| false),
+            (0, vec![])
+        );
+
+        // A single hexagon is a loop covering all six edges.
+        let (length, trail) = super::longest_trail(axial!(0, 0).edges(), |_| true, |_| false);
+        assert_eq!(length, 6);
+        assert!(is_trail(&trail));
+
+        // Predicate filters the candidates.
+        let (length, trail) = super::longest_trail(
+            axial!(0, 0).edges(),
+            |edge| edge.dir != EdgeDirection::West,
+            |_| false,
+        );
+        assert_eq!(length, 2);
+        assert!(is_trail(&trail));
+
+        // A blocking vertex on the loop splits it into a single path around.
+        let (length, trail) = super::longest_trail(
+            axial!(0, 0).edges(),
+            |_| true,
+            |vert| *vert == vertex!(0, 0, VertexSpin::Up),
+        );
+        assert_eq!(length, 6);
+        assert!(is_trail(&trail));
+
+        // Blocking two vertices of the loop leaves the longest arc.
+        let (length, trail) = super::longest_trail(
+            axial!(0, 0).edges(),
+            |_| true,
+            |vert| *vert == vertex!(0, 0, VertexSpin::Up) || *vert == vertex!(0, 1, VertexSpin::Up),
+        );
+        assert_eq!(length, 4);
+        assert!(is_trail(&trail));
+    }
+
+    #[test]
+    fn longest_trail_branches() {
+        // A fork where one branch is longer than the other, the short branch must be dropped.
+        let spine = [
+            edge!(0, 0, EdgeDirection::NorthWest),
+            edge!(0, 0, EdgeDirection::NorthEast),
+            edge!(1, 0, EdgeDirection::NorthWest),
+            edge!(1, 0, EdgeDirection::NorthEast),
+        ];
+        let spur = [edge!(1, 0, EdgeDirection::West)];
+
+        let (length, trail) = super::longest_trail(
+            spine.iter().chain(spur.iter()).copied(),
+            |_| true,
+            |_| false,
+        );
+        assert_eq!(length, 4);
+        assert!(is_trail(&trail));
+        assert!(!trail.contains(&spur[0]));
+
+        // Duplicate edges are only counted once.
+        let (length, _) = super::longest_trail(
+            spine.iter().chain(spine.iter()).copied(),
+            |_| true,
+            |_| false,
+        );
+        assert_eq!(length, 4);
+
+        // Two hexagons sharing an edge, a trail can revisit vertices but not edges.
+        let network = axial!(0, 0)
+            .edges()
+            .into_iter()
+            .chain(axial!(1, 0).edges())
+            .collect::<Vec<Edge>>();
+        let (length, trail) = super::longest_trail(network, |_| true, |_| false);
+        assert_eq!(length, 11);
+        assert!(is_trail(&trail));
+    }
+
+    #[test]
+    fn longest_trail_starts() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        // Starting from the ends of trails finds the same length as starting from every vertex.
+        let candidates: BTreeSet<Edge> = axial!(0, 0)
+            .range(1)
+            .into_iter()
+            .flat_map(|coord| coord.edges())
+            .collect();
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..40 {
+            let edges: Vec<Edge> = candidates
+                .iter()
+                .copied()
+                .filter(|_| rng.gen_bool(0.4))
+                .collect();
+            let mut incidence: BTreeMap<Vertex, Vec<usize>> = BTreeMap::new();
+            for (idx, edge) in edges.iter().enumerate() {
+                for vert in edge.endpoints() {
+                    incidence.entry(vert).or_default().push(idx);
+                }
+            }
+            let blocked: BTreeSet<Vertex> = incidence
+                .keys()
+                .copied()
+                .filter(|_| rng.gen_bool(0.1))
+                .collect();
+
+            let mut search = TrailSearch {
+                used: vec![false; edges.len()],
+                edges: edges.clone(),
+                incidence,
+                blocked: blocked.clone(),
+                path: vec![],
+                best: vec![],
+            };
+            let every: Vec<Vertex> = search.incidence.keys().copied().collect();
+            for start in every {
+                search.extend(start);
+            }
+
+            let (length, trail) =
+                super::longest_trail(edges, |_| true, |vert| blocked.contains(vert));
+            assert_eq!(length, search.best.len());
+            assert!(is_trail(&trail));
+        }
+
+        // A loop apart from the rest of the network is still searched.
+        let network = axial!(0, 0)
+            .edges()
+            .into_iter()
+            .chain([edge!(5, 0, EdgeDirection::West)]);
+        assert_eq!(super::longest_trail(network, |_| true, |_| false).0, 6);
+    }
+
+    #[test]
+    fn movement_range() {
+        // No walls matches the plain range.
+        for range in 0..4 {
+            let mut expected = axial!(1, -1).range(range);
+            expected.sort();
+            assert_eq!(
+                super::movement_range(axial!(1, -1), range, |coord| coord.neighbors()),
+                expected
+            );
+        }
+
+        // Walled in on every side.
+        let walls = axial!(0, 0).edges();
+        assert_eq!(
+            super::movement_range(axial!(0, 0), 3, |coord| coord
+                .movement_neighbors(|edge| walls.contains(edge))),
+            vec![axial!(0, 0)]
+        );
+
+        // An enclosed area stops expanding long before a huge range.
+        assert_eq!(
+            super::movement_range(axial!(0, 0), i32::MAX, |coord| coord
+                .movement_neighbors(|edge| walls.contains(edge))),
+            vec![axial!(0, 0)]
+        );
+        let mut room = axial!(0, 0).range(2);
+        room.sort();
+        assert_eq!(
+            super::movement_range(axial!(0, 0), i32::MAX, |coord| coord
+                .neighbors()
+                .into_iter()
+                .filter(|neighbor| room.contains(neighbor))
+                .collect::<Vec<_>>()),
+            room
+        );
+
+        // A single wall means the hex behind it takes the long way around.
+        let wall = axial!(0, 0).edge(HexDirection::Front);
+        let reach = super::movement_range(axial!(0, 0), 1, |coord| {
+            coord.movement_neighbors(|edge| *edge == wall)
+        });
+        assert_eq!(reach.len(), 6);
+        assert!(!reach.contains(&axial!(1, 0)));
+        assert!(super::movement_range(axial!(0, 0), 2, |coord| {
+            coord.movement_neighbors(|edge| *edge == wall)
+        })
+        .contains(&axial!(1, 0)));
+    }
+
+    #[test]
+    fn line_of_sight() {
+        let open = |coord: &Axial| coord.neighbors();
+        assert!(super::line_of_sight(axial!(0, 0), axial!(0, 0), open));
+        assert!(super::line_of_sight(axial!(0, 0), axial!(3, -1), open));
+
+        let wall = axial!(1, 0).edge(HexDirection::Front);
+        let walled = |coord: &Axial| coord.movement_neighbors(|edge| *edge == wall);
+        assert!(!super::line_of_sight(axial!(0, 0), axial!(3, 0), walled));
+        assert!(!super::line_of_sight(axial!(3, 0), axial!(0, 0), walled));
+        assert!(super::line_of_sight(axial!(0, 0), axial!(1, 0), walled));
+        assert!(super::line_of_sight(axial!(0, 1), axial!(3, 1), walled));
+    }
+
+    #[test]
+    fn find_path() {
+        let open = |coord: &Axial| coord.neighbors();
+        assert_eq!(
+            super::find_path(axial!(0, 0), axial!(0, 0), 0, open),
+            Some(vec![axial!(0, 0)])
+        );
+        assert_eq!(
+            super::find_path(axial!(0, 0), axial!(3, 0), 5, open),
+            Some(vec![axial!(0, 0), axial!(1, 0), axial!(2, 0), axial!(3, 0)])
+        );
+        assert_eq!(super::find_path(axial!(0, 0), axial!(3, 0), 2, open), None);
+
+        // Around a wall.
+        let wall = axial!(0, 0).edge(HexDirection::Front);
+        assert_eq!(
+            super::find_path(axial!(0, 0), axial!(1, 0), 10, |coord| coord
+                .movement_neighbors(|edge| *edge == wall)),
+            Some(vec![axial!(0, 0), axial!(0, 1), axial!(1, 0)])
+        );
+
+        // Enclosed, the search is bounded so this terminates.
+        let walls = axial!(0, 0).edges();
+        assert_eq!(
+            super::find_path(axial!(0, 0), axial!(2, 0), 10, |coord| coord
+                .movement_neighbors(|edge| walls.contains(edge))),
+            None
+        );
+        assert_eq!(
+            super::find_path(axial!(2, 0), axial!(0, 0), 10, |coord| coord
+                .movement_neighbors(|edge| walls.contains(edge))),
+            None
+        );
+    }
+
+    #[test]
+    fn outline() {
+        assert_eq!(super::outline([]), Outline::default());
+
+        // A single hex is its own boundary.
+        let single = super::outline([axial!(0, 0)]);
+        assert_eq!(single.hexes, vec![axial!(0, 0)]);
+        assert_eq!(single.edges.len(), 6);
+        assert!(single.holes.is_empty());
+        let mut expected = axial!(0, 0).vertices().to_vec();
+        let least = (0..6).min_by_key(|idx| expected[*idx]).unwrap();
+        expected.rotate_left(least);
+        assert_eq!(single.outer, vec![expected]);
+
+        // Two hexes share an edge that is not part of the boundary.
+        let pair = super::outline([axial!(0, 0), axial!(1, 0)]);
+        assert_eq!(pair.edges.len(), 10);
+        assert!(!pair.edges.contains(&axial!(0, 0).edge(HexDirection::Front)));
+        assert_eq!(pair.outer.len(), 1);
+        assert_eq!(pair.outer[0].len(), 10);
+
+        // A hexagon of radius 2 only has its outer ring on the boundary.
+        let hexagon = super::outline(axial!(0, 0).range(2));
+        assert_eq!(hexagon.hexes.len(), 12);
+        assert_eq!(hexagon.edges.len(), 30);
+        assert_eq!(hexagon.outer.len(), 1);
+        assert!(hexagon.holes.is_empty());
+
+        // A ring has a hole the shape of the missing center.
+        let ring = super::outline(axial!(0, 0).neighbors());
+        assert_eq!(ring.hexes.len(), 6);
+        assert_eq!(ring.outer.len(), 1);
+        assert_eq!(ring.outer[0].len(), 18);
+        assert_eq!(ring.holes.len(), 1);
+        let mut hole: Vec<Vertex> = ring.holes[0].clone();
+        hole.sort();
+        let mut center = axial!(0, 0).vertices().to_vec();
+        center.sort();
+        assert_eq!(hole, center);
+
+        // Disconnected parts each have an outer loop.
+        let parts = super::outline([axial!(0, 0), axial!(5, 0)]);
+        assert_eq!(parts.outer.len(), 2);
+
+        // Consecutive vertices in every loop are adjacent.
+        for lp in ring.outer.iter().chain(ring.holes.iter()) {
+            for idx in 0..lp.len() {
+                assert!(lp[idx].edge_to(lp[(idx + 1) % lp.len()]).is_some());
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Hex aligned bounds of coordinates.
+
+use crate::lib::*;
+
+use crate::axial;
+
+use super::coordinate::Axial;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The minimum and maximum of each of the q, r and s components of a set of coordinates.
+///
+/// These are the three pairs of linear inequalities that bound the coordinates along the axes of the grid.
+/// The coordinates inside the bounds form the smallest hex aligned convex region around the set, which can be
+/// anything from a line or triangle to a hexagon.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug)]
+pub struct HexBounds {
+    /// Minimum q component.
+    pub q_min: i32,
+    /// Maximum q component.
+    pub q_max: i32,
+    /// Minimum r component.
+    pub r_min: i32,
+    /// Maximum r component.
+    pub r_max: i32,
+    /// Minimum s component.
+    pub s_min: i32,
+    /// Maximum s component.
+    pub s_max: i32,
+}
+
+impl HexBounds {
+    /// Compute the bounds of some coordinates.
+    ///
+    /// None if there are no coordinates.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::bounds::HexBounds;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// // Bounds of a triangle of size 1
+    /// let bounds = HexBounds::from_points([axial!(0, 0), axial!(0, 1), axial!(1, 0)]);
+    /// ```
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Axial>,
+    {
+        points.into_iter().fold(None, |bounds, point| {
+            let s = point.compute_s();
+            Some(match bounds {
+                None => Self {
+                    q_min: point.q,
+                    q_max: point.q,
+                    r_min: point.r,
+                    r_max: point.r,
+                    s_min: s,
+                    s_max: s,
+                },
+                Some(bounds) => Self {
+                    q_min: bounds.q_min.min(point.q),
+                    q_max: bounds.q_max.max(point.q),
+                    r_min: bounds.r_min.min(point.r),
+                    r_max: bounds.r_max.max(point.r),
+                    s_min: bounds.s_min.min(s),
+                    s_max: bounds.s_max.max(s),
+                },
+            })
+        })
+    }
+
+    /// Check if a coordinate is inside the bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::bounds::HexBounds;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// let bounds = HexBounds::from_points([axial!(0, 0), axial!(2, 0)]).unwrap();
+    /// assert!(bounds.contains(axial!(1, 0)));
+    /// ```
+    pub fn contains(&self, coord: Axial) -> bool {
+        let s = coord.compute_s();
+        (self.q_min..=self.q_max).contains(&coord.q)
+            && (self.r_min..=self.r_max).contains(&coord.r)
+            && (self.s_min..=self.s_max).contains(&s)
+    }
+
+    /// Generate every coordinate inside the bounds.
+    ///
+    /// Coordinates are sorted by q then r.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::bounds::HexBounds;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// // The two points and the tile between them.
+    /// let hexes = HexBounds::from_points([axial!(0, 0), axial!(2, 0)]).unwrap().hexes();
+    /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn hexes(&self) -> Vec<Axial> {
+        (self.q_min..=self.q_max)
+            .flat_map(|q| {
+                (self.r_min.max(-q - self.s_max)..=self.r_max.min(-q - self.s_min))
+                    .map(move |r| axial!(q, r))
+            })
+            .collect()
+    }
+
+    /// Find the smallest regular hexagon that contains the bounds.
+    ///
+    /// Returns the center and size of the hexagon, every coordinate within `size` of the center covers the
+    /// bounds. When there is more than one center the least by q then r is chosen.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::bounds::HexBounds;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// // A line of 3 tiles fits in a hexagon of size 1 around its middle tile.
+    /// let bounds = HexBounds::from_points([axial!(0, 0), axial!(2, 0)]).unwrap();
+    /// assert_eq!(bounds.enclosing_hexagon(), (axial!(1, 0), 1));
+    /// ```
+    pub fn enclosing_hexagon(&self) -> (Axial, i32) {
+        let div_ceil = |a: i32, b: i32| -(-a).div_euclid(b);
+
+        // Each axis needs half its span and the center has to satisfy q + r + s = 0.
+        let size = div_ceil(self.q_max - self.q_min, 2)
+            .max(div_ceil(self.r_max - self.r_min, 2))
+            .max(div_ceil(self.s_max - self.s_min, 2))
+            .max(div_ceil(self.q_max + self.r_max + self.s_max, 3))
+            .max(div_ceil(-(self.q_min + self.r_min + self.s_min), 3));
+
+        // Every component of the center must be within size of both of its bounds.
+        for q in self.q_max - size..=self.q_min + size {
+            let r_low = (self.r_max - size).max(-q - (self.s_min + size));
+            let r_high = (self.r_min + size).min(-q - (self.s_max - size));
+            if r_low <= r_high {
+                return (axial!(q, r_low), size);
+            }
+        }
+
+        unreachable!() // The size is always large enough for a center to exist.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_points() {
+        assert_eq!(HexBounds::from_points([]), None);
+        assert_eq!(
+            HexBounds::from_points([axial!(0, 0), axial!(0, 1), axial!(1, 0)]),
+            Some(HexBounds {
+                q_min: 0,
+                q_max: 1,
+                r_min: 0,
+                r_max: 1,
+                s_min: -1,
+                s_max: 0
+            })
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let bounds = HexBounds::from_points([axial!(0, 0), axial!(0, 1), axial!(1, 0)]).unwrap();
+        assert!(bounds.contains(axial!(0, 0)));
+        assert!(bounds.contains(axial!(1, 0)));
+        assert!(!bounds.contains(axial!(1, 1)));
+        assert!(!bounds.contains(axial!(-1, 0)));
+    }
+
+    #[test]
+    fn hexes() {
+        let bounds = HexBounds::from_points([axial!(0, 0), axial!(0, 1), axial!(1, 0)]).unwrap();
+        assert_eq!(
+            bounds.hexes(),
+            vec![axial!(0, 0), axial!(0, 1), axial!(1, 0)]
+        );
+
+        let bounds = HexBounds::from_points(axial!(3, -1).range(2)).unwrap();
+        let mut expected = axial!(3, -1).range(2);
+        expected.sort();
+        assert_eq!(bounds.hexes(), expected);
+    }
+
+    #[test]
+    fn enclosing_hexagon() {
+        assert_eq!(
+            HexBounds::from_points(axial!(3, -1).range(2))
+                .unwrap()
+                .enclosing_hexagon(),
+            (axial!(3, -1), 2)
+        );
+        assert_eq!(
+            HexBounds::from_points([axial!(4, 4)])
+                .unwrap()
+                .enclosing_hexagon(),
+            (axial!(4, 4), 0)
+        );
+
+        // Compare against every center and size near the points.
+        let point_sets = [
+            vec![axial!(0, 0), axial!(1, 0)],
+            vec![axial!(0, 0), axial!(0, 1), axial!(1, 0)],
+            vec![axial!(0, 0), axial!(3, -1), axial!(-1, 2)],
+            vec![axial!(-2, 0), axial!(2, -2), axial!(1, 3), axial!(0, 1)],
+        ];
+        for points in point_sets {
+            let bounds = HexBounds::from_points(points.iter().copied()).unwrap();
+            let (center, size) = bounds.enclosing_hexagon();
+            assert!(bounds
+                .hexes()
+                .iter()
+                .all(|hex| hex.distance(center) <= size));
+
+            let smallest = axial!(0, 0)
+                .range(6)
+                .into_iter()
+                .map(|center| {
+                    bounds
+                        .hexes()
+                        .iter()
+                        .map(|hex| hex.distance(center))
+                        .max()
+                        .unwrap()
+                })
+                .min()
+                .unwrap();
+            assert_eq!(size, smallest);
+        }
+    }
+}
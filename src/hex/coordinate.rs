@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// The coordinate system is similar but not fully analogus to cartesian 3D X, Y, Z.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug, Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug, Default)]
 pub struct Axial {
     /// q (x) coordinate
     pub q: i32,
@@ -45,7 +45,7 @@ pub use axial;
 ///
 /// Positive q is the forward vector for a tile, meaning these directions are in relation to that.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum HexDirection {
     /// Direction denoting positive q (x) axis
     Front,
@@ -117,6 +117,8 @@ impl HexDirection {
 }
 
 /// Represents the three axes of symmetry in hexagons.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum Axes {
     /// q axis of a hexagonal grid
     Q,
@@ -187,7 +189,7 @@ impl Axial {
     ///
     /// Scale has no meaning with a point so we do not scale here.
     ///
-    /// The order of applications is rotation then translation.
+    /// The order of applications is reflection across the q axis, rotation then translation.
     ///
     /// # Example
     /// ```
@@ -197,7 +199,11 @@ impl Axial {
     /// let new_coord = axial!(2, 5).apply_transform(transform!(axial!(1, 1), 4));
     /// ```
     pub fn apply_transform(&self, transform: Transform<Self>) -> Self {
-        self.rotate(None, transform.rotation) + transform.translation
+        let coord = match transform.reflected {
+            true => self.reflect(None, Axes::Q),
+            false => *self,
+        };
+        coord.rotate(None, transform.rotation) + transform.translation
     }
 
     /// Make a vector from its components.
@@ -332,6 +338,67 @@ impl Axial {
         ]
     }
 
+    /// Get the edge on a side of this tile.
+    ///
+    /// See [`HexDirection`] for a reference of directionality.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+    ///
+    /// // The edge between (0, 0) and (1, 0)
+    /// let edge = axial!(0, 0).edge(HexDirection::Front);
+    /// ```
+    pub fn edge(&self, direction: HexDirection) -> Edge {
+        let offset = Edge::from(direction);
+        edge!(self.q + offset.q, self.r + offset.r, offset.dir)
+    }
+
+    /// Given two neighboring tiles produce the shared edge.
+    ///
+    /// See [`Edge`].
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// let edge = axial!(0, 0).shared_edge(axial!(1, 0));
+    /// ```
+    pub fn shared_edge(&self, b: Self) -> Option<Edge> {
+        (0..6)
+            .map(HexDirection::from)
+            .find(|dir| self.neighbor(*dir) == b)
+            .map(|dir| self.edge(dir))
+    }
+
+    /// Get the neighbors that can be moved to from this coordinate.
+    ///
+    /// `is_blocked` marks edges that can not be crossed, such as walls or rivers. A neighbor is only
+    /// reachable if the edge shared with it is open.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+    /// use gridava::hex::edge::Edge;
+    ///
+    /// // A wall on the front side of (0, 0)
+    /// let wall = axial!(0, 0).edge(HexDirection::Front);
+    ///
+    /// // Five of the six neighbors are reachable.
+    /// let coords = axial!(0, 0).movement_neighbors(|edge: &Edge| *edge == wall);
+    /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn movement_neighbors<F>(&self, mut is_blocked: F) -> Vec<Self>
+    where
+        F: FnMut(&Edge) -> bool,
+    {
+        (0..6)
+            .map(HexDirection::from)
+            .filter(|dir| !is_blocked(&self.edge(*dir)))
+            .map(|dir| self.neighbor(dir))
+            .collect()
+    }
+
     /// Given two neighboring tiles produce the shared vertices.
     ///
     /// See [`Vertex`].
@@ -557,6 +624,75 @@ impl Axial {
         ret
     }
 
+    /// Calculate the vertices that form a line along hex borders between two hex centers.
+    ///
+    /// The line starts on the corner of `self` closest to `b` and ends on the corner of `b` closest to
+    /// `self`, see [`Vertex::line`] for how the corners are joined. Neighboring hexes share their closest
+    /// corner so their line is that single vertex. The result is the same in either direction, only reversed.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// let verts = axial!(0, 0).vertex_line(axial!(3, 0));
+    /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn vertex_line(&self, b: Self) -> Vec<Vertex> {
+        // Always walk from the lesser hex so the line is the same in both directions.
+        if b < *self {
+            let mut ret = b.vertex_line(*self);
+            ret.reverse();
+            return ret;
+        }
+
+        // Squared distance in the scaled space of the vertices.
+        let dist = |from: (i64, i64), to: (i64, i64)| {
+            let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+            3 * dx * dx + dy * dy
+        };
+        let center = |hex: Self| {
+            let (x, y) = Vertex::scaled_center(hex);
+            (3 * x, 3 * y)
+        };
+
+        let start = self
+            .vertices()
+            .into_iter()
+            .min_by_key(|vert| (dist(vert.scaled_position(), center(b)), *vert))
+            .expect("a hex always has vertices");
+        let end = b
+            .vertices()
+            .into_iter()
+            .min_by_key(|vert| {
+                (
+                    dist(vert.scaled_position(), center(*self)),
+                    dist(vert.scaled_position(), start.scaled_position()),
+                    *vert,
+                )
+            })
+            .expect("a hex always has vertices");
+
+        start.line(end)
+    }
+
+    /// Calculate the edges that form a line along hex borders between two hex centers.
+    ///
+    /// See [`Axial::vertex_line`], the edges connect each consecutive vertex of that line.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// let edges = axial!(0, 0).edge_line(axial!(3, 0));
+    /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn edge_line(&self, b: Self) -> Vec<Edge> {
+        self.vertex_line(b)
+            .windows(2)
+            .filter_map(|pair| pair[0].edge_to(pair[1]))
+            .collect()
+    }
+
     /// Calculate all the coordinates within a range.
     ///
     /// # Example
@@ -661,6 +797,42 @@ impl Axial {
     }
 }
 
+impl Transform<Axial> {
+    /// Combine a transform with a transform relative to it.
+    ///
+    /// Applying the result is the same as applying `child` then `self`. Rotation is kept within 0 to 5 and
+    /// scales are multiplied.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::core::transform::{Transform, transform, Vector2D, vector2d};
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// let parent = transform!(axial!(4, 0), 1);
+    /// let child = transform!(axial!(1, 0));
+    ///
+    /// let world = parent.compose(child);
+    /// assert_eq!(world.translation, axial!(4, 1));
+    /// ```
+    pub fn compose(self, child: Transform<Axial>) -> Transform<Axial> {
+        // Reflecting reverses the direction of any rotation that came before it.
+        let rotation = match self.reflected {
+            true => self.rotation - child.rotation,
+            false => self.rotation + child.rotation,
+        };
+
+        let mut scale = self.scale;
+        scale *= child.scale;
+
+        Transform {
+            translation: child.translation.apply_transform(self),
+            rotation: rotation.rem_euclid(6),
+            scale,
+            reflected: self.reflected ^ child.reflected,
+        }
+    }
+}
+
 impl Add for Axial {
     type Output = Axial;
 
@@ -729,6 +901,32 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn compose() {
+        let transforms = [
+            transform!(axial!(0, 0)),
+            transform!(axial!(2, -1), 1),
+            transform!(axial!(-3, 4), 4, vector2d!(1.0), true),
+            transform!(axial!(1, 1), -2, vector2d!(2.0), true),
+        ];
+
+        for parent in transforms {
+            for child in transforms {
+                let composed = parent.compose(child);
+                for coord in axial!(0, 0).range(2) {
+                    assert_eq!(
+                        coord.apply_transform(composed),
+                        coord.apply_transform(child).apply_transform(parent)
+                    );
+                }
+            }
+        }
+
+        let scaled = transforms[3].compose(transforms[3]);
+        assert_eq!(scaled.scale, vector2d!(4.0));
+        assert!(!scaled.reflected);
+    }
+
     #[test]
     fn axial_macro() {
         assert_eq!(Axial { q: 4, r: 3 }, axial!(4, 3));
@@ -774,6 +972,12 @@ mod tests {
         let transform = transform!(axial!(1, 1), 1);
         assert_eq!(axial!(0, 0).apply_transform(transform), axial!(1, 1));
         assert_eq!(axial!(1, 1).apply_transform(transform), axial!(0, 3));
+
+        // Reflection happens before rotation.
+        let transform = transform!(axial!(1, 1), 1, vector2d!(1.0), true);
+        assert_eq!(axial!(0, 0).apply_transform(transform), axial!(1, 1));
+        assert_eq!(axial!(1, 0).apply_transform(transform), axial!(2, 1));
+        assert_eq!(axial!(0, 1).apply_transform(transform), axial!(2, 0));
     }
 
     #[test]
@@ -949,6 +1153,66 @@ mod tests {
         );
     }
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn vertex_line() {
+        // Neighbors meet at a shared corner.
+        assert_eq!(
+            axial!(0, 0).vertex_line(axial!(1, 0)),
+            vec![vertex!(0, 1, VertexSpin::Up)]
+        );
+
+        // Along the q axis the line follows the border between two rows.
+        assert_eq!(
+            axial!(0, 0).vertex_line(axial!(2, 0)),
+            vec![
+                vertex!(0, 1, VertexSpin::Up),
+                vertex!(1, 0, VertexSpin::Down),
+                vertex!(1, 1, VertexSpin::Up)
+            ]
+        );
+
+        let hexes = axial!(0, 0).range(3);
+        for a in &hexes {
+            for b in &hexes {
+                let line = a.vertex_line(*b);
+                assert!(a.vertices().contains(line.first().unwrap()));
+                assert!(b.vertices().contains(line.last().unwrap()));
+                assert!(line
+                    .windows(2)
+                    .all(|pair| pair[0].adjacent_vertices().contains(&pair[1])));
+
+                let mut reverse = b.vertex_line(*a);
+                reverse.reverse();
+                assert_eq!(line, reverse);
+            }
+        }
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn edge_line() {
+        assert_eq!(axial!(0, 0).edge_line(axial!(1, 0)), vec![]);
+        assert_eq!(
+            axial!(0, 0).edge_line(axial!(2, 0)),
+            vec![
+                edge!(0, 1, EdgeDirection::NorthEast),
+                edge!(1, 1, EdgeDirection::NorthWest)
+            ]
+        );
+
+        let hexes = axial!(0, 0).range(3);
+        for a in &hexes {
+            for b in &hexes {
+                let edges = a.edge_line(*b);
+                assert_eq!(edges.len() + 1, a.vertex_line(*b).len());
+                assert!(edges
+                    .windows(2)
+                    .all(|pair| pair[0].adjacent_edges().contains(&pair[1])));
+            }
+        }
+    }
+
     #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn range() {
@@ -1066,6 +1330,57 @@ mod tests {
         )
     }
 
+    #[test]
+    fn edge() {
+        for (i, edge) in axial!(0, 0).edges().iter().enumerate() {
+            // edges() starts at the front left side.
+            assert_eq!(axial!(0, 0).edge(HexDirection::from(i as i32 - 1)), *edge);
+        }
+
+        assert_eq!(
+            axial!(2, -3).edge(HexDirection::Front),
+            edge!(3, -3, EdgeDirection::West)
+        );
+    }
+
+    #[test]
+    fn shared_edge() {
+        assert_eq!(axial!(0, 0).shared_edge(axial!(0, 0)), None);
+        assert_eq!(axial!(0, 0).shared_edge(axial!(2, 0)), None);
+
+        for neighbor in axial!(1, 1).neighbors() {
+            let edge = axial!(1, 1).shared_edge(neighbor).unwrap();
+            assert_eq!(Some(edge), neighbor.shared_edge(axial!(1, 1)));
+            assert!(edge.adjacent_hexes().contains(&axial!(1, 1)));
+            assert!(edge.adjacent_hexes().contains(&neighbor));
+        }
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn movement_neighbors() {
+        assert_eq!(
+            axial!(0, 0).movement_neighbors(|_| false),
+            axial!(0, 0).neighbors().to_vec()
+        );
+        assert_eq!(axial!(0, 0).movement_neighbors(|_| true), vec![]);
+
+        let wall = edge!(0, 0, EdgeDirection::West);
+        assert_eq!(
+            axial!(0, 0).movement_neighbors(|edge| *edge == wall),
+            vec![
+                axial!(1, 0),
+                axial!(0, 1),
+                axial!(-1, 1),
+                axial!(0, -1),
+                axial!(1, -1)
+            ]
+        );
+        assert!(!axial!(-1, 0)
+            .movement_neighbors(|edge| *edge == wall)
+            .contains(&axial!(0, 0)));
+    }
+
     #[test]
     fn shared_vert_two() {
         assert!(axial!(0, 0).shared_vert_two(axial!(1, 1)).is_none());
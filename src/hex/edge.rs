@@ -1,9 +1,9 @@
 //! Handles edges in a hexagonal grid.
 
-// use crate::vertex;
+use crate::core::transform::Transform;
 
 use super::{
-    coordinate::{axial, Axial, HexDirection},
+    coordinate::{axial, Axes, Axial, HexDirection},
     vertex::{vertex, Vertex, VertexSpin},
 };
 
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 
 /// Orientation of an edge.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
 pub enum EdgeDirection {
     /// West of the hex.
     West,
@@ -24,7 +24,7 @@ pub enum EdgeDirection {
 
 /// A hexagonal edge.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
 pub struct Edge {
     /// q (x) coordinate
     pub q: i32,
@@ -71,6 +71,81 @@ impl From<HexDirection> for Edge {
 }
 
 impl Edge {
+    /// Rotate an edge.
+    ///
+    /// `center` Optionally can specify a hex to rotate about. None will rotate about (0, 0).
+    ///
+    /// `rot_dir`: positive denotes CW, negative CCW, magnitude denotes how many 60 degree rotations.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::edge::{Edge, EdgeDirection, edge};
+    ///
+    /// // edge will be the back left edge of (0, 0), (0, 0, NorthWest)
+    /// let edge = edge!(0, 0, EdgeDirection::West).rotate(None, 1);
+    /// ```
+    pub fn rotate(&self, center: Option<Axial>, rot_dir: i32) -> Self {
+        // The edge is one of the three back sides of its own hex, rotate that hex and step the side around.
+        let dir: i32 = match self.dir {
+            EdgeDirection::West => HexDirection::Back.into(),
+            EdgeDirection::NorthWest => HexDirection::BackLeft.into(),
+            EdgeDirection::NorthEast => HexDirection::FrontLeft.into(),
+        };
+        let hex = axial!(self.q, self.r).rotate(center, rot_dir);
+        let offset = Edge::from(HexDirection::from(dir + rot_dir));
+
+        edge!(hex.q + offset.q, hex.r + offset.r, offset.dir)
+    }
+
+    /// Reflect an edge.
+    ///
+    /// `center` Optionally can specify a hex to reflect about. None will reflect about (0, 0).
+    ///
+    /// See [`Axial::reflect`].
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::Axes;
+    /// use gridava::hex::edge::{Edge, EdgeDirection, edge};
+    ///
+    /// // edge will be the north west edge of (0, 0)
+    /// let edge = edge!(0, 0, EdgeDirection::NorthEast).reflect(None, Axes::R);
+    /// ```
+    pub fn reflect(&self, center: Option<Axial>, axes: Axes) -> Self {
+        let [a, b] = self.adjacent_hexes();
+        // Neighbors stay neighbors under reflection.
+        a.reflect(center, axes)
+            .shared_edge(b.reflect(center, axes))
+            .unwrap()
+    }
+
+    /// Applies a transform matrix to this edge.
+    ///
+    /// Scale has no meaning with an edge so we do not scale here.
+    ///
+    /// The order of applications is reflection across the q axis, rotation then translation.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::edge::{Edge, EdgeDirection, edge};
+    /// use gridava::core::transform::{Transform, transform, Vector2D, vector2d};
+    ///
+    /// let new_edge = edge!(2, 5, EdgeDirection::West).apply_transform(transform!(axial!(1, 1), 4));
+    /// ```
+    pub fn apply_transform(&self, transform: Transform<Axial>) -> Self {
+        let edge = match transform.reflected {
+            true => self.reflect(None, Axes::Q),
+            false => *self,
+        };
+        let edge = edge.rotate(None, transform.rotation);
+        edge!(
+            edge.q + transform.translation.q,
+            edge.r + transform.translation.r,
+            edge.dir
+        )
+    }
+
     /// Get the adjacent hexes that share this edge.
     ///
     /// The first coordinate in the array will always be the (q, r) coordinate.
@@ -153,6 +228,10 @@ impl Edge {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        core::transform::{vector2d, Vector2D},
+        transform,
+    };
 
     #[test]
     fn default() {
@@ -192,6 +271,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rotate() {
+        // Every side of a hex steps to the next side CW.
+        let sides = axial!(0, 0).edges();
+        for i in 0..6 {
+            assert_eq!(sides[i].rotate(None, 1), sides[(i + 1) % 6]);
+            assert_eq!(sides[i].rotate(None, -1), sides[(i + 5) % 6]);
+            assert_eq!(sides[i].rotate(None, 6), sides[i]);
+        }
+
+        // The hexes either side of an edge rotate with it.
+        let edge = edge!(2, -1, EdgeDirection::NorthEast);
+        for rot in -6..=6 {
+            let mut expected = edge
+                .adjacent_hexes()
+                .map(|hex| hex.rotate(Some(axial!(1, 1)), rot));
+            let mut actual = edge.rotate(Some(axial!(1, 1)), rot).adjacent_hexes();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn apply_transform() {
+        let transform = transform!(axial!(1, 1), 1);
+        assert_eq!(
+            edge!(0, 0, EdgeDirection::West).apply_transform(transform),
+            edge!(1, 1, EdgeDirection::NorthWest)
+        );
+        assert_eq!(
+            edge!(1, 0, EdgeDirection::West).apply_transform(transform),
+            edge!(1, 2, EdgeDirection::NorthWest)
+        );
+    }
+
     #[test]
     fn adjacent_hexes() {
         assert_eq!(
@@ -277,4 +392,44 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn reflect() {
+        assert_eq!(
+            edge!(0, 0, EdgeDirection::NorthEast).reflect(None, Axes::R),
+            edge!(0, 0, EdgeDirection::NorthWest)
+        );
+
+        // The reflected edge lies between the reflected hexes.
+        for axes in [Axes::Q, Axes::R, Axes::S] {
+            for hex in axial!(0, 0).range(2) {
+                for edge in hex.edges() {
+                    let reflected = edge.reflect(Some(axial!(1, -1)), axes);
+                    let mut hexes = reflected.adjacent_hexes();
+                    hexes.sort();
+                    let mut expected = edge
+                        .adjacent_hexes()
+                        .map(|hex| hex.reflect(Some(axial!(1, -1)), axes));
+                    expected.sort();
+                    assert_eq!(hexes, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn apply_transform_reflected() {
+        let transform = transform!(axial!(1, 1), 1, vector2d!(1.0), true);
+        for hex in axial!(0, 0).range(2) {
+            for edge in hex.edges() {
+                let mut hexes = edge.apply_transform(transform).adjacent_hexes();
+                hexes.sort();
+                let mut expected = edge
+                    .adjacent_hexes()
+                    .map(|hex| hex.apply_transform(transform));
+                expected.sort();
+                assert_eq!(hexes, expected);
+            }
+        }
+    }
 }
@@ -4,8 +4,6 @@ use crate::lib::*;
 
 use super::coordinate::Axial;
 
-use crate::axial;
-
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -0,0 +1,496 @@
+//! Maze generation on hex grids using edges as walls.
+//!
+//! Requires the `rand` feature.
+
+use crate::lib::*;
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    coordinate::{Axial, HexDirection},
+    edge::Edge,
+    shape::HexShape,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The algorithm used to carve a maze.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum MazeAlgorithm {
+    /// Randomized depth first search that backtracks at dead ends, produces long winding corridors.
+    RecursiveBacktracker,
+    /// Randomized Prim's algorithm, grows out from a single tile and produces many short branches.
+    Prim,
+    /// Randomized Kruskal's algorithm, joins random regions together until only one remains.
+    Kruskal,
+    /// Wilson's algorithm, uses loop erased random walks so every perfect maze is equally likely.
+    Wilson,
+}
+
+/// A maze over a set of hexes.
+///
+/// Every edge touching a hex of the maze is either open, a passage between two hexes, or closed, a wall.
+/// The edges between the maze and hexes outside of it are always closed.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Maze {
+    /// Edges that can be walked through.
+    pub open: BTreeSet<Edge>,
+    /// Edges that are walls.
+    pub closed: BTreeSet<Edge>,
+}
+
+impl Maze {
+    /// Generate a maze over a set of hexes.
+    ///
+    /// `braid` is the chance, from `0.0` to `1.0`, that a dead end is removed by opening one of its walls.
+    /// A braid of `0.0` makes a perfect maze, where there is exactly one path between any two hexes. Higher
+    /// values add loops. Values outside of the range are clamped to it, a braid that is not finite is treated
+    /// as `0.0`.
+    ///
+    /// If the hexes are not connected each connected region gets its own maze.
+    ///
+    /// The maze only depends on the hexes and the state of `rng`, so a seeded rng will always produce the same
+    /// maze.
+    ///
+    /// # Example
+    /// ```
+    /// use rand::{rngs::StdRng, SeedableRng};
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::maze::{Maze, MazeAlgorithm};
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let maze = Maze::generate(axial!(0, 0).range(4), MazeAlgorithm::Wilson, 0.0, &mut rng);
+    /// ```
+    pub fn generate<I, R>(hexes: I, algorithm: MazeAlgorithm, braid: f64, rng: &mut R) -> Self
+    where
+        I: IntoIterator<Item = Axial>,
+        R: Rng + ?Sized,
+    {
+        let cells: BTreeSet<Axial> = hexes.into_iter().collect();
+        let mut open = BTreeSet::new();
+
+        for region in regions(&cells) {
+            match algorithm {
+                MazeAlgorithm::RecursiveBacktracker => {
+                    recursive_backtracker(&region, &cells, rng, &mut open)
+                }
+                MazeAlgorithm::Prim => prim(&region, &cells, rng, &mut open),
+                MazeAlgorithm::Kruskal => kruskal(&region, &cells, rng, &mut open),
+                MazeAlgorithm::Wilson => wilson(&region, &cells, rng, &mut open),
+            }
+        }
+
+        braid_maze(&cells, braid, rng, &mut open);
+
+        let closed = cells
+            .iter()
+            .flat_map(|cell| cell.edges())
+            .filter(|edge| !open.contains(edge))
+            .collect();
+
+        Self { open, closed }
+    }
+
+    /// Generate a maze over the tiles of a shape.
+    ///
+    /// The shape's transform is applied so the maze is in the coordinate space of the shape's parent.
+    ///
+    /// See [`Maze::generate`].
+    ///
+    /// # Example
+    /// ```
+    /// use rand::{rngs::StdRng, SeedableRng};
+    /// use gridava::hex::maze::{Maze, MazeAlgorithm};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let shape = HexShape::make_rhombus(5, 0, false, |_| ());
+    /// let maze = Maze::from_shape(&shape, MazeAlgorithm::Kruskal, 0.25, &mut rng);
+    /// ```
+    pub fn from_shape<T, R>(
+        shape: &HexShape<T>,
+        algorithm: MazeAlgorithm,
+        braid: f64,
+        rng: &mut R,
+    ) -> Self
+    where
+        T: Clone,
+        R: Rng + ?Sized,
+    {
+        Self::generate(shape.iter().map(|(coord, _)| coord), algorithm, braid, rng)
+    }
+
+    /// Check if an edge can be walked through.
+    ///
+    /// Edges that do not touch the maze are not open.
+    pub fn is_open(&self, edge: &Edge) -> bool {
+        self.open.contains(edge)
+    }
+}
+
+/// The neighbors of a cell that are part of the maze and the edges shared with them.
+fn passages(cell: Axial, cells: &BTreeSet<Axial>) -> Vec<(Axial, Edge)> {
+    (0..6)
+        .map(HexDirection::from)
+        .map(|dir| (cell.neighbor(dir), cell.edge(dir)))
+        .filter(|(neighbor, _)| cells.contains(neighbor))
+        .collect()
+}
+
+/// Split cells into their connected regions, each region is sorted.
+fn regions(cells: &BTreeSet<Axial>) -> Vec<Vec<Axial>> {
+    let mut seen = BTreeSet::new();
+    let mut ret = vec![];
+
+    for cell in cells {
+        if !seen.insert(*cell) {
+            continue;
+        }
+
+        let mut region = vec![*cell];
+        let mut stack = vec![*cell];
+        while let Some(current) = stack.pop() {
+            for (neighbor, _) in passages(current, cells) {
+                if seen.insert(neighbor) {
+                    region.push(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        region.sort();
+        ret.push(region);
+    }
+
+    ret
+}
+
+fn recursive_backtracker<R: Rng + ?Sized>(
+    region: &[Axial],
+    cells: &BTreeSet<Axial>,
+    rng: &mut R,
+    open: &mut BTreeSet<Edge>,
+) {
+    let start = region[rng.gen_range(0..region.len())];
+    let mut visited = BTreeSet::from([start]);
+    let mut stack = vec![start];
+
+    while let Some(&cell) = stack.last() {
+        let options: Vec<(Axial, Edge)> = passages(cell, cells)
+            .into_iter()
+            .filter(|(neighbor, _)| !visited.contains(neighbor))
+            .collect();
+
+        // Dead end, backtrack.
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (next, edge) = options[rng.gen_range(0..options.len())];
+        open.insert(edge);
+        visited.insert(next);
+        stack.push(next);
+    }
+}
+
+fn prim<R: Rng + ?Sized>(
+    region: &[Axial],
+    cells: &BTreeSet<Axial>,
+    rng: &mut R,
+    open: &mut BTreeSet<Edge>,
+) {
+    let start = region[rng.gen_range(0..region.len())];
+    let mut visited = BTreeSet::from([start]);
+    let mut frontier = passages(start, cells);
+
+    while !frontier.is_empty() {
+        let (next, edge) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if visited.insert(next) {
+            open.insert(edge);
+            frontier.extend(
+                passages(next, cells)
+                    .into_iter()
+                    .filter(|(neighbor, _)| !visited.contains(neighbor)),
+            );
+        }
+    }
+}
+
+fn kruskal<R: Rng + ?Sized>(
+    region: &[Axial],
+    cells: &BTreeSet<Axial>,
+    rng: &mut R,
+    open: &mut BTreeSet<Edge>,
+) {
+    // Disjoint set over the region, indexed by position in the region.
+    fn find(parent: &mut [usize], idx: usize) -> usize {
+        let mut root = idx;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        // Path compression.
+        let mut idx = idx;
+        while parent[idx] != root {
+            let next = parent[idx];
+            parent[idx] = root;
+            idx = next;
+        }
+        root
+    }
+
+    let index: BTreeMap<Axial, usize> = region
+        .iter()
+        .enumerate()
+        .map(|(idx, cell)| (*cell, idx))
+        .collect();
+    let mut parent: Vec<usize> = (0..region.len()).collect();
+
+    // Every wall between two cells, once.
+    let mut walls: Vec<(usize, usize, Edge)> = region
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, cell)| {
+            passages(*cell, cells)
+                .into_iter()
+                .filter(move |(neighbor, _)| neighbor > cell)
+                .map(move |(neighbor, edge)| (idx, neighbor, edge))
+        })
+        .map(|(idx, neighbor, edge)| (idx, index[&neighbor], edge))
+        .collect();
+    walls.shuffle(rng);
+
+    for (a, b, edge) in walls {
+        let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+        if root_a != root_b {
+            parent[root_a] = root_b;
+            open.insert(edge);
+        }
+    }
+}
+
+fn wilson<R: Rng + ?Sized>(
+    region: &[Axial],
+    cells: &BTreeSet<Axial>,
+    rng: &mut R,
+    open: &mut BTreeSet<Edge>,
+) {
+    let mut in_maze = BTreeSet::from([region[rng.gen_range(0..region.len())]]);
+
+    for cell in region {
+        // Random walk until the maze is hit, only remembering the last exit from each cell. Following the
+        // exits afterwards gives the walk with its loops erased.
+        let mut exits: BTreeMap<Axial, (Axial, Edge)> = BTreeMap::new();
+        let mut current = *cell;
+        while !in_maze.contains(&current) {
+            let options = passages(current, cells);
+            let exit = options[rng.gen_range(0..options.len())];
+            exits.insert(current, exit);
+            current = exit.0;
+        }
+
+        // Carve the loop erased walk into the maze.
+        let mut current = *cell;
+        while !in_maze.contains(&current) {
+            let (next, edge) = exits[&current];
+            open.insert(edge);
+            in_maze.insert(current);
+            current = next;
+        }
+    }
+}
+
+/// Remove dead ends by opening one of their walls, preferring walls into another dead end.
+fn braid_maze<R: Rng + ?Sized>(
+    cells: &BTreeSet<Axial>,
+    braid: f64,
+    rng: &mut R,
+    open: &mut BTreeSet<Edge>,
+) {
+    let braid = match braid.is_finite() {
+        true => braid.clamp(0.0, 1.0),
+        false => 0.0,
+    };
+    if braid == 0.0 {
+        return;
+    }
+
+    let open_count = |cell: Axial, open: &BTreeSet<Edge>| {
+        passages(cell, cells)
+            .iter()
+            .filter(|(_, edge)| open.contains(edge))
+            .count()
+    };
+
+    for cell in cells {
+        if open_count(*cell, open) != 1 || !rng.gen_bool(braid) {
+            continue;
+        }
+
+        let walls: Vec<(Axial, Edge)> = passages(*cell, cells)
+            .into_iter()
+            .filter(|(_, edge)| !open.contains(edge))
+            .collect();
+        let dead_ends: Vec<(Axial, Edge)> = walls
+            .iter()
+            .filter(|(neighbor, _)| open_count(*neighbor, open) == 1)
+            .copied()
+            .collect();
+
+        let pool = if dead_ends.is_empty() {
+            walls
+        } else {
+            dead_ends
+        };
+        if !pool.is_empty() {
+            open.insert(pool[rng.gen_range(0..pool.len())].1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{axial, hex::algorithms::movement_range};
+
+    const ALGORITHMS: [MazeAlgorithm; 4] = [
+        MazeAlgorithm::RecursiveBacktracker,
+        MazeAlgorithm::Prim,
+        MazeAlgorithm::Kruskal,
+        MazeAlgorithm::Wilson,
+    ];
+
+    // Every hex that can be walked to from start through open edges.
+    fn walkable(maze: &Maze, start: Axial) -> Vec<Axial> {
+        movement_range(start, i32::MAX, |coord| {
+            coord.movement_neighbors(|edge| !maze.is_open(edge))
+        })
+    }
+
+    #[test]
+    fn perfect() {
+        let hexes = axial!(0, 0).range(3);
+
+        for algorithm in ALGORITHMS {
+            let maze = Maze::generate(hexes.clone(), algorithm, 0.0, &mut StdRng::seed_from_u64(7));
+
+            // A spanning tree, connected with no loops.
+            assert_eq!(maze.open.len(), hexes.len() - 1);
+            assert_eq!(walkable(&maze, axial!(0, 0)).len(), hexes.len());
+
+            // Every edge of the region is accounted for once.
+            let all: BTreeSet<Edge> = hexes.iter().flat_map(|hex| hex.edges()).collect();
+            assert_eq!(maze.open.len() + maze.closed.len(), all.len());
+            assert!(maze.open.is_disjoint(&maze.closed));
+
+            // The outer wall is closed.
+            assert!(maze
+                .closed
+                .contains(&axial!(3, 0).edge(HexDirection::Front)));
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        let hexes = axial!(0, 0).range(3);
+
+        for algorithm in ALGORITHMS {
+            let a = Maze::generate(hexes.clone(), algorithm, 0.5, &mut StdRng::seed_from_u64(1));
+            let b = Maze::generate(hexes.clone(), algorithm, 0.5, &mut StdRng::seed_from_u64(1));
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn braid() {
+        let hexes = axial!(0, 0).range(3);
+        let cells: BTreeSet<Axial> = hexes.iter().copied().collect();
+
+        for algorithm in ALGORITHMS {
+            let maze = Maze::generate(hexes.clone(), algorithm, 1.0, &mut StdRng::seed_from_u64(3));
+
+            // No dead ends and more passages than a perfect maze.
+            assert!(maze.open.len() > hexes.len() - 1);
+            for hex in &hexes {
+                let open = passages(*hex, &cells)
+                    .iter()
+                    .filter(|(_, edge)| maze.is_open(edge))
+                    .count();
+                assert!(open > 1);
+            }
+        }
+    }
+
+    #[test]
+    fn braid_not_finite() {
+        let hexes = axial!(0, 0).range(3);
+
+        // Not finite is a perfect maze, rather than a panic in the rng.
+        for braid in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let maze = Maze::generate(
+                hexes.clone(),
+                MazeAlgorithm::RecursiveBacktracker,
+                braid,
+                &mut StdRng::seed_from_u64(3),
+            );
+            let perfect = Maze::generate(
+                hexes.clone(),
+                MazeAlgorithm::RecursiveBacktracker,
+                0.0,
+                &mut StdRng::seed_from_u64(3),
+            );
+            assert_eq!(maze, perfect);
+        }
+    }
+
+    #[test]
+    fn disconnected() {
+        let hexes: Vec<Axial> = axial!(0, 0)
+            .range(1)
+            .into_iter()
+            .chain(axial!(10, 0).range(1))
+            .chain([axial!(-10, 0)])
+            .collect();
+
+        for algorithm in ALGORITHMS {
+            let maze = Maze::generate(hexes.clone(), algorithm, 0.0, &mut StdRng::seed_from_u64(5));
+
+            // A spanning tree for each region.
+            assert_eq!(maze.open.len(), hexes.len() - 3);
+            assert_eq!(walkable(&maze, axial!(0, 0)).len(), 7);
+            assert_eq!(walkable(&maze, axial!(10, 0)).len(), 7);
+            assert_eq!(walkable(&maze, axial!(-10, 0)).len(), 1);
+        }
+
+        assert_eq!(
+            Maze::generate(
+                [],
+                MazeAlgorithm::Wilson,
+                0.0,
+                &mut StdRng::seed_from_u64(5)
+            ),
+            Maze::default()
+        );
+    }
+
+    #[test]
+    fn from_shape() {
+        let mut shape = HexShape::make_hexagon(2, 0, false, |_| 1);
+        shape.translate(axial!(5, 5));
+
+        let maze = Maze::from_shape(
+            &shape,
+            MazeAlgorithm::Prim,
+            0.0,
+            &mut StdRng::seed_from_u64(9),
+        );
+
+        assert_eq!(maze.open.len(), 18);
+        assert_eq!(walkable(&maze, axial!(7, 7)).len(), 19);
+    }
+}
@@ -0,0 +1,284 @@
+//! Geometric measurements of shapes.
+
+use crate::lib::*;
+
+use super::{
+    bounds::HexBounds, coordinate::Axial, shape::HexShape, storage::ShapeStorage, vertex::Vertex,
+};
+
+#[cfg(feature = "std")]
+use super::grid::WSConverter;
+
+/// Measurements of a shape's tiles in the parent space.
+///
+/// The shape's transform is applied, so a rotated or translated shape is measured where it is placed.
+impl<T: Clone, S: ShapeStorage<T>> HexShape<T, S> {
+    /// Count the tiles in the shape.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// assert_eq!(HexShape::make_hexagon(1, 0, false, |_| 1).area(), 7);
+    /// ```
+    pub fn area(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Compute the average coordinate of the shape's tiles.
+    ///
+    /// Returns a fractional (q, r) coordinate, see [`Axial::round`] for the nearest tile. None if the shape
+    /// has no tiles.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // The center tile (1, 1)
+    /// let centroid = HexShape::make_hexagon(1, 0, false, |_| 1).centroid();
+    /// ```
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let area = self.area();
+        if area == 0 {
+            return None;
+        }
+
+        let (q, r) = self.iter().fold((0i64, 0i64), |(q, r), (coord, _)| {
+            (q + coord.q as i64, r + coord.r as i64)
+        });
+        Some((q as f64 / area as f64, r as f64 / area as f64))
+    }
+
+    /// Compute the average position of the shape's tiles in world space.
+    ///
+    /// See [`WSConverter::hex_to_world`].
+    ///
+    /// ```
+    /// use gridava::hex::grid::{WSConverter, HexOrientation};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let converter = WSConverter { size: 32.0, orientation: HexOrientation::PointyTop };
+    /// let position = HexShape::make_hexagon(1, 0, false, |_| 1).world_centroid(&converter);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn world_centroid(&self, converter: &WSConverter) -> Option<(f64, f64)> {
+        let area = self.area();
+        if area == 0 {
+            return None;
+        }
+
+        let (x, y) = self.iter().fold((0.0, 0.0), |(x, y), (coord, _)| {
+            let (tx, ty) = converter.hex_to_world(coord);
+            (x + tx, y + ty)
+        });
+        Some((x / area as f64, y / area as f64))
+    }
+
+    /// Compute the hex aligned bounds of the shape's tiles.
+    ///
+    /// The tiles inside the bounds are the smallest hex aligned convex region containing the shape. None if
+    /// the shape has no tiles.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let bounds = HexShape::make_triangle(2, 0, false, |_| 1).bounds();
+    /// ```
+    pub fn bounds(&self) -> Option<HexBounds> {
+        HexBounds::from_points(self.iter().map(|(coord, _)| coord))
+    }
+
+    /// Find the smallest regular hexagon containing the shape.
+    ///
+    /// Returns the center and size of the hexagon, see [`HexBounds::enclosing_hexagon`]. None if the shape
+    /// has no tiles.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // A triangle of size 2 fits in a hexagon of size 2.
+    /// let (center, size) = HexShape::make_triangle(2, 0, false, |_| 1).enclosing_hexagon().unwrap();
+    /// ```
+    pub fn enclosing_hexagon(&self) -> Option<(Axial, i32)> {
+        self.bounds().map(|bounds| bounds.enclosing_hexagon())
+    }
+
+    /// Find the convex hull of the shape's tile centers.
+    ///
+    /// Returns the tiles at the corners of the hull in clockwise order, starting from the least tile. Tiles
+    /// lying along a side of the hull are not included. A shape of one or two tiles, or a line, returns its
+    /// end tiles.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // The three corners of the triangle.
+    /// let corners = HexShape::make_triangle(3, 0, false, |_| 1).convex_hull();
+    /// assert_eq!(corners.len(), 3);
+    /// ```
+    pub fn convex_hull(&self) -> Vec<Axial> {
+        let mut points: Vec<Axial> = self.iter().map(|(coord, _)| coord).collect();
+        points.sort();
+        points.dedup();
+
+        if points.len() < 3 {
+            return points;
+        }
+
+        // Cross product of the turn a -> b -> c in a space with equal length axes, positive is clockwise.
+        let cross = |a: Axial, b: Axial, c: Axial| {
+            let (ax, ay) = Vertex::scaled_center(a);
+            let (bx, by) = Vertex::scaled_center(b);
+            let (cx, cy) = Vertex::scaled_center(c);
+            (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+        };
+
+        // Monotone chain, the least tile by q then r is also the least along the world x axis.
+        let mut order = points.clone();
+        order.sort_by_key(|coord| {
+            let (x, y) = Vertex::scaled_center(*coord);
+            (x, y)
+        });
+
+        let mut hull: Vec<Axial> = vec![];
+        for pass in [order.clone(), order.into_iter().rev().collect()] {
+            let start = hull.len();
+            for point in pass {
+                while hull.len() >= start + 2
+                    && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+                {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            hull.pop();
+        }
+
+        // Every tile is on one line.
+        if hull.len() < 3 {
+            return vec![points[0], points[points.len() - 1]];
+        }
+
+        let least = (0..hull.len()).min_by_key(|idx| hull[*idx]).unwrap();
+        hull.rotate_left(least);
+        hull
+    }
+
+    /// Count the edges between the shape and the outside.
+    ///
+    /// This counts the sides of holes as well, see [`Self::outline`] for the edges themselves.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// assert_eq!(HexShape::make_hexagon(1, 0, false, |_| 1).perimeter(), 18);
+    /// ```
+    pub fn perimeter(&self) -> usize {
+        self.iter()
+            .map(|(coord, _)| {
+                coord
+                    .neighbors()
+                    .iter()
+                    .filter(|neighbor| !self.contains(**neighbor))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{axial, hex::grid::HexOrientation};
+
+    #[test]
+    fn area() {
+        assert_eq!(HexShape::<i32>::new(None, None).area(), 0);
+        assert_eq!(HexShape::make_hexagon(2, 0, true, |_| 1).area(), 19);
+        assert_eq!(HexShape::make_triangle(2, 0, true, |_| 1).area(), 6);
+    }
+
+    #[test]
+    fn centroid() {
+        assert_eq!(HexShape::<i32>::new(None, None).centroid(), None);
+
+        let mut shape = HexShape::make_hexagon(2, 0, false, |_| 1);
+        shape.translate(axial!(-5, 1));
+        assert_eq!(shape.centroid(), Some((-3.0, 3.0)));
+
+        let shape = HexShape::make_line(1, 0, false, |_| 1);
+        assert_eq!(shape.centroid(), Some((0.5, 0.0)));
+
+        let converter = WSConverter {
+            size: 10.0,
+            orientation: HexOrientation::PointyTop,
+        };
+        let (x, y) = shape.world_centroid(&converter).unwrap();
+        let (ax, ay) = converter.hex_to_world(axial!(0, 0));
+        let (bx, by) = converter.hex_to_world(axial!(1, 0));
+        assert_eq!((x, y), ((ax + bx) / 2.0, (ay + by) / 2.0));
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(HexShape::<i32>::new(None, None).bounds(), None);
+
+        // Bounds round trip through make_shape.
+        let mut shape = HexShape::make_triangle(3, 2, false, |_| 1);
+        shape.translate(axial!(2, 2));
+        let bounds = shape.bounds().unwrap();
+        let mut tiles: Vec<Axial> = shape.iter().map(|(coord, _)| coord).collect();
+        tiles.sort();
+        assert_eq!(bounds.hexes(), tiles);
+
+        let (center, size) = HexShape::make_hexagon(3, 0, false, |_| 1)
+            .enclosing_hexagon()
+            .unwrap();
+        assert_eq!((center, size), (axial!(3, 3), 3));
+    }
+
+    #[test]
+    fn convex_hull() {
+        assert!(HexShape::<i32>::new(None, None).convex_hull().is_empty());
+        assert_eq!(
+            HexShape::make_line(3, 1, false, |_| 1).convex_hull(),
+            vec![axial!(0, 0), axial!(0, 3)]
+        );
+
+        let hull = HexShape::make_hexagon(2, 0, false, |_| 1).convex_hull();
+        assert_eq!(
+            hull,
+            vec![
+                axial!(0, 2),
+                axial!(2, 0),
+                axial!(4, 0),
+                axial!(4, 2),
+                axial!(2, 4),
+                axial!(0, 4)
+            ]
+        );
+
+        // A concave shape's hull skips the inside corner.
+        let mut shape = HexShape::make_rhombus(2, 0, false, |_| 1);
+        shape.get_hexes_mut()[[2, 2]] = None;
+        shape.get_hexes_mut()[[1, 2]] = None;
+        shape.get_hexes_mut()[[2, 1]] = None;
+        assert_eq!(
+            shape.convex_hull(),
+            vec![axial!(0, 0), axial!(2, 0), axial!(0, 2)]
+        );
+    }
+
+    #[test]
+    fn perimeter() {
+        assert_eq!(HexShape::<i32>::new(None, None).perimeter(), 0);
+        assert_eq!(HexShape::make_line(0, 0, false, |_| 1).perimeter(), 6);
+        assert_eq!(HexShape::make_line(1, 0, false, |_| 1).perimeter(), 10);
+
+        let shape = HexShape::make_hexagon(2, 0, false, |_| 1);
+        assert_eq!(shape.perimeter(), shape.outline().edges.len());
+
+        // Holes count towards the perimeter.
+        let mut ring = shape.clone();
+        ring.get_hexes_mut()[[2, 2]] = None;
+        assert_eq!(ring.perimeter(), shape.perimeter() + 6);
+    }
+}
@@ -2,10 +2,36 @@
 //!
 //! TODO: Examples.
 //!
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod algorithms;
+pub mod bounds;
 pub mod coordinate;
 pub mod edge;
 #[cfg(feature = "std")]
 pub mod grid;
+#[cfg(all(feature = "rand", any(feature = "std", feature = "alloc")))]
+pub mod maze;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod metrics;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod morphology;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod movement;
+pub mod orientable;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod placement;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod polygon;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod polyhex;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod prefab;
 #[cfg(any(feature = "std", feature = "alloc"))]
 pub mod shape;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod storage;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod symmetry;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod tree;
 pub mod vertex;
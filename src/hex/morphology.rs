@@ -0,0 +1,444 @@
+//! Morphological operations on regions of a hex grid.
+//!
+//! Regions can be sets of coordinates or the tiles of a [`HexShape`]. A structuring element is a set of
+//! offsets from the origin, such as `axial!(0, 0).range(1)` for a hexagon of size 1.
+
+use crate::lib::*;
+
+use crate::axial;
+
+use super::{coordinate::Axial, shape::HexShape, storage::ShapeStorage};
+
+/// Compute the Minkowski sum of two sets of coordinates.
+///
+/// Every coordinate of `a` is offset by every coordinate of `b`. This is the dilation of `a` by `b`.
+///
+/// # Example
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::morphology::minkowski_sum;
+///
+/// // A line of 3 tiles with a ring of tiles around it.
+/// let grown = minkowski_sum([axial!(0, 0), axial!(1, 0), axial!(2, 0)], axial!(0, 0).range(1));
+/// ```
+pub fn minkowski_sum<A, B>(a: A, b: B) -> BTreeSet<Axial>
+where
+    A: IntoIterator<Item = Axial>,
+    B: IntoIterator<Item = Axial>,
+{
+    let b: Vec<Axial> = b.into_iter().collect();
+    a.into_iter()
+        .flat_map(|coord| b.iter().map(move |offset| coord + *offset))
+        .collect()
+}
+
+/// Compute the Minkowski difference of two sets of coordinates.
+///
+/// The coordinates that stay inside `a` when offset by every coordinate of `b`. This is the erosion of
+/// `a` by `b`. An empty `b` gives an empty result.
+///
+/// # Example
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::morphology::minkowski_difference;
+///
+/// // Only the center of a hexagon of size 1 fits a hexagon of size 1.
+/// let center = minkowski_difference(axial!(0, 0).range(1), axial!(0, 0).range(1));
+/// assert_eq!(center.into_iter().collect::<Vec<_>>(), vec![axial!(0, 0)]);
+/// ```
+pub fn minkowski_difference<A, B>(a: A, b: B) -> BTreeSet<Axial>
+where
+    A: IntoIterator<Item = Axial>,
+    B: IntoIterator<Item = Axial>,
+{
+    let a: BTreeSet<Axial> = a.into_iter().collect();
+    let b: Vec<Axial> = b.into_iter().collect();
+
+    let first = match b.first() {
+        Some(first) => *first,
+        None => return BTreeSet::new(),
+    };
+
+    // Any result must land in `a` when offset by the first coordinate of `b`.
+    a.iter()
+        .map(|coord| *coord - first)
+        .filter(|coord| b.iter().all(|offset| a.contains(&(*coord + *offset))))
+        .collect()
+}
+
+/// Grow a set of coordinates by a radius.
+///
+/// Adds every coordinate within `radius` of the set, see [`Axial::range`].
+///
+/// # Example
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::morphology::dilate;
+///
+/// // The border of a territory pushed out by 2 tiles.
+/// let territory = dilate([axial!(0, 0), axial!(1, 0)], 2);
+/// ```
+pub fn dilate<I>(coords: I, radius: u32) -> BTreeSet<Axial>
+where
+    I: IntoIterator<Item = Axial>,
+{
+    minkowski_sum(coords, axial!(0, 0).range(radius as i32))
+}
+
+/// Shrink a set of coordinates by a radius.
+///
+/// Keeps only the coordinates that have every coordinate within `radius` in the set, see [`Axial::range`].
+///
+/// # Example
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::morphology::erode;
+///
+/// // A hexagon of size 3 shrunk to a hexagon of size 1.
+/// let zone = erode(axial!(0, 0).range(3), 2);
+/// ```
+pub fn erode<I>(coords: I, radius: u32) -> BTreeSet<Axial>
+where
+    I: IntoIterator<Item = Axial>,
+{
+    minkowski_difference(coords, axial!(0, 0).range(radius as i32))
+}
+
+/// Open a set of coordinates by a structuring element.
+///
+/// Erodes then dilates, removing parts of the set too thin to hold the structuring element.
+///
+/// # Example
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::morphology::open;
+///
+/// // The spur sticking out of the hexagon is removed.
+/// let mut land = axial!(0, 0).range(2);
+/// land.push(axial!(3, 0));
+/// let smoothed = open(land, axial!(0, 0).range(1));
+/// ```
+pub fn open<I, S>(coords: I, structure: S) -> BTreeSet<Axial>
+where
+    I: IntoIterator<Item = Axial>,
+    S: IntoIterator<Item = Axial>,
+{
+    let structure: Vec<Axial> = structure.into_iter().collect();
+    minkowski_sum(
+        minkowski_difference(coords, structure.iter().copied()),
+        structure,
+    )
+}
+
+/// Close a set of coordinates by a structuring element.
+///
+/// Dilates then erodes, filling gaps and bays too small to hold the structuring element.
+///
+/// # Example
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::morphology::close;
+///
+/// // The hole in the ring is filled.
+/// let filled = close(axial!(0, 0).neighbors(), axial!(0, 0).range(1));
+/// ```
+pub fn close<I, S>(coords: I, structure: S) -> BTreeSet<Axial>
+where
+    I: IntoIterator<Item = Axial>,
+    S: IntoIterator<Item = Axial>,
+{
+    let structure: Vec<Axial> = structure.into_iter().collect();
+    minkowski_difference(minkowski_sum(coords, structure.iter().copied()), structure)
+}
+
+/// Morphological operations on the tiles of a shape.
+///
+/// A structuring shape's tiles, in its parent space, are the offsets. Translate a shape so its center is at
+/// (0, 0) to grow or shrink evenly.
+///
+/// Results are in the parent space and tightly bound, see [`HexShape::from_tiles`]. Tiles that were in the
+/// original shape keep their data as it is stored, new tiles are made with `constructor`. The results lose
+/// the shape's rotation without orienting the data, use [`HexShape::bake_oriented`] first for data with a
+/// direction.
+impl<T: Clone, S: ShapeStorage<T>> HexShape<T, S> {
+    // Build a shape from parent space coordinates, keeping the data of tiles in this shape.
+    fn with_tiles<F>(&self, coords: BTreeSet<Axial>, mut constructor: F) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        Self::from_tiles(coords.into_iter().map(|coord| match self.get(coord) {
+            Some(tile) => (coord, tile.clone()),
+            None => (coord, constructor(coord)),
+        }))
+    }
+
+    // Tiles of a shape in the parent space.
+    fn coords(&self) -> impl Iterator<Item = Axial> + '_ {
+        self.iter().map(|(coord, _)| coord)
+    }
+
+    /// Grow the shape by a radius.
+    ///
+    /// See [`dilate`].
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // A hexagon of size 3, the new ring of tiles is set to 0.
+    /// let my_shape = HexShape::make_hexagon(2, 0, false, |_| 1).dilate(1, |_| 0);
+    /// ```
+    pub fn dilate<F>(&self, radius: u32, constructor: F) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        self.with_tiles(dilate(self.coords(), radius), constructor)
+    }
+
+    /// Shrink the shape by a radius.
+    ///
+    /// See [`erode`].
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // A hexagon of size 1.
+    /// let my_shape = HexShape::make_hexagon(2, 0, false, |_| 1).erode(1);
+    /// ```
+    pub fn erode(&self, radius: u32) -> Self {
+        // The radius always includes (0, 0) so every tile is from the original shape.
+        self.with_tiles(erode(self.coords(), radius), |_| unreachable!())
+    }
+
+    /// Grow the shape by a structuring shape.
+    ///
+    /// This is the Minkowski sum of the shapes, see [`minkowski_sum`].
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // Grow only along the q axis.
+    /// let mut structure = HexShape::make_line(2, 0, false, |_| ());
+    /// structure.translate(axial!(-1, 0));
+    ///
+    /// let my_shape = HexShape::make_hexagon(2, 0, false, |_| 1).dilate_by(&structure, |_| 0);
+    /// ```
+    pub fn dilate_by<U, US, F>(&self, structure: &HexShape<U, US>, constructor: F) -> Self
+    where
+        U: Clone,
+        US: ShapeStorage<U>,
+        F: FnMut(Axial) -> T,
+    {
+        self.with_tiles(
+            minkowski_sum(self.coords(), structure.coords()),
+            constructor,
+        )
+    }
+
+    /// Shrink the shape by a structuring shape.
+    ///
+    /// This is the Minkowski difference of the shapes, see [`minkowski_difference`]. If the structure does not
+    /// contain (0, 0) the result can move outside of the shape, those tiles are made with `constructor`.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // Shrink only along the q axis.
+    /// let mut structure = HexShape::make_line(2, 0, false, |_| ());
+    /// structure.translate(axial!(-1, 0));
+    ///
+    /// let my_shape = HexShape::make_hexagon(2, 0, false, |_| 1).erode_by(&structure, |_| 0);
+    /// ```
+    pub fn erode_by<U, US, F>(&self, structure: &HexShape<U, US>, constructor: F) -> Self
+    where
+        U: Clone,
+        US: ShapeStorage<U>,
+        F: FnMut(Axial) -> T,
+    {
+        self.with_tiles(
+            minkowski_difference(self.coords(), structure.coords()),
+            constructor,
+        )
+    }
+
+    /// Open the shape by a structuring shape.
+    ///
+    /// See [`open`].
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut structure = HexShape::make_hexagon(1, 0, false, |_| ());
+    /// structure.translate(axial!(-1, -1));
+    ///
+    /// let smoothed = HexShape::make_hexagon(3, 0, false, |_| 1).open(&structure);
+    /// ```
+    pub fn open<U: Clone, US: ShapeStorage<U>>(&self, structure: &HexShape<U, US>) -> Self {
+        // An opening never grows the shape so every tile is from the original shape.
+        self.with_tiles(open(self.coords(), structure.coords()), |_| unreachable!())
+    }
+
+    /// Close the shape by a structuring shape.
+    ///
+    /// See [`close`].
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut structure = HexShape::make_hexagon(1, 0, false, |_| ());
+    /// structure.translate(axial!(-1, -1));
+    ///
+    /// let filled = HexShape::make_hexagon(3, 0, false, |_| 1).close(&structure, |_| 0);
+    /// ```
+    pub fn close<U, US, F>(&self, structure: &HexShape<U, US>, constructor: F) -> Self
+    where
+        U: Clone,
+        US: ShapeStorage<U>,
+        F: FnMut(Axial) -> T,
+    {
+        self.with_tiles(close(self.coords(), structure.coords()), constructor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set<I: IntoIterator<Item = Axial>>(coords: I) -> BTreeSet<Axial> {
+        coords.into_iter().collect()
+    }
+
+    fn centered_hexagon(size: u32) -> HexShape<()> {
+        let mut shape = HexShape::make_hexagon(size, 0, false, |_| ());
+        shape.translate(axial!(-(size as i32), -(size as i32)));
+        shape
+    }
+
+    #[test]
+    fn minkowski_sum() {
+        assert_eq!(
+            super::minkowski_sum([axial!(0, 0)], axial!(0, 0).range(2)),
+            set(axial!(0, 0).range(2))
+        );
+        assert_eq!(
+            super::minkowski_sum([axial!(1, 0), axial!(0, 3)], [axial!(0, 0), axial!(0, 1)]),
+            set([axial!(1, 0), axial!(1, 1), axial!(0, 3), axial!(0, 4)])
+        );
+        assert!(super::minkowski_sum([axial!(1, 0)], []).is_empty());
+    }
+
+    #[test]
+    fn minkowski_difference() {
+        assert_eq!(
+            super::minkowski_difference(axial!(0, 0).range(3), axial!(0, 0).range(1)),
+            set(axial!(0, 0).range(2))
+        );
+
+        // Offsets that are not centered move the result.
+        assert_eq!(
+            super::minkowski_difference(
+                [axial!(0, 0), axial!(1, 0), axial!(2, 0)],
+                [axial!(0, 0), axial!(1, 0)]
+            ),
+            set([axial!(0, 0), axial!(1, 0)])
+        );
+
+        assert!(super::minkowski_difference(axial!(0, 0).range(1), []).is_empty());
+    }
+
+    #[test]
+    fn dilate_erode() {
+        assert_eq!(
+            dilate(axial!(2, -1).range(1), 2),
+            set(axial!(2, -1).range(3))
+        );
+        assert_eq!(
+            erode(axial!(2, -1).range(3), 2),
+            set(axial!(2, -1).range(1))
+        );
+        assert_eq!(erode(axial!(0, 0).range(1), 2), BTreeSet::new());
+
+        // Radius 0 changes nothing.
+        assert_eq!(
+            dilate(axial!(0, 0).neighbors(), 0),
+            set(axial!(0, 0).neighbors())
+        );
+        assert_eq!(
+            erode(axial!(0, 0).neighbors(), 0),
+            set(axial!(0, 0).neighbors())
+        );
+
+        // A ring has no tile with all its neighbors.
+        assert!(erode(axial!(0, 0).neighbors(), 1).is_empty());
+    }
+
+    #[test]
+    fn open_close() {
+        let mut land = axial!(0, 0).range(2);
+        land.push(axial!(3, 0));
+        assert_eq!(
+            super::open(land.clone(), axial!(0, 0).range(1)),
+            set(axial!(0, 0).range(2))
+        );
+
+        // Closing fills the hole in a ring.
+        assert_eq!(
+            super::close(axial!(0, 0).neighbors(), axial!(0, 0).range(1)),
+            set(axial!(0, 0).range(1))
+        );
+
+        // Opening never adds and closing never removes.
+        let opened = super::open(land.clone(), [axial!(0, 0), axial!(1, 0)]);
+        assert!(opened.is_subset(&set(land.clone())));
+        let closed = super::close(land.clone(), [axial!(0, 0), axial!(1, 0)]);
+        assert!(closed.is_superset(&set(land)));
+    }
+
+    #[test]
+    fn shape() {
+        let mut shape = HexShape::make_hexagon(2, 0, false, |_| 1);
+        shape.translate(axial!(3, 3));
+        let center = axial!(5, 5);
+
+        let grown = shape.dilate(1, |_| 0);
+        assert_eq!(grown.iter().count(), 37);
+        assert_eq!(grown.get(center), Some(&1));
+        assert_eq!(grown.get(axial!(8, 5)), Some(&0));
+
+        let shrunk = shape.erode(1);
+        assert_eq!(
+            set(shrunk.iter().map(|(coord, _)| coord)),
+            set(center.range(1))
+        );
+        assert!(shrunk.iter().all(|(_, tile)| *tile == 1));
+
+        assert_eq!(shape.dilate_by(&centered_hexagon(1), |_| 0), grown);
+        assert_eq!(shape.erode_by(&centered_hexagon(1), |_| 0), shrunk);
+
+        // A structure away from the origin moves the result.
+        let mut offset = HexShape::make_line(0, 0, false, |_| ());
+        offset.translate(axial!(3, 0));
+        let moved = shape.erode_by(&offset, |_| 0);
+        assert_eq!(moved.iter().count(), 19);
+        assert_eq!(moved.get(axial!(2, 5)), Some(&0));
+        assert_eq!(moved.get(axial!(4, 5)), Some(&1));
+
+        // Closing fills a hole with new tiles.
+        let mut ring = shape.clone();
+        ring.get_hexes_mut()[[2, 2]] = None;
+        let filled = ring.close(&centered_hexagon(1), |_| 5);
+        assert_eq!(filled.iter().count(), 19);
+        assert_eq!(filled.get(center), Some(&5));
+
+        assert_eq!(
+            set(shape
+                .open(&centered_hexagon(1))
+                .iter()
+                .map(|(coord, _)| coord)),
+            set(center.range(2))
+        );
+    }
+}
@@ -0,0 +1,352 @@
+//! Moving shapes along paths.
+//!
+//! Large units cover more than one tile, so a path that is clear for a single tile may not be clear for the
+//! unit. [`HexShape::follow_path`] walks a shape's footprint along a path of coordinates, like one from
+//! [`find_path`](super::algorithms::find_path), and finds where it first runs into something.
+
+use crate::lib::*;
+
+use crate::{
+    axial,
+    core::{collection::ReadCollection, transform::Transform},
+};
+
+use super::{coordinate::Axial, shape::HexShape, storage::ShapeStorage};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How a shape turns as it moves along a path.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Facing {
+    /// Keep the rotation of the shape's transform.
+    Fixed,
+    /// Turn so the shape's local q axis points along each move, like a vehicle.
+    Forward,
+}
+
+/// A position of a shape moving along a path.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Pose {
+    /// Index of the path step the shape is moving to.
+    pub step: usize,
+    /// The shape's transform in this pose, see [`HexShape::set_origin`].
+    pub transform: Transform<Axial>,
+    /// The coordinates covered by the shape, in the same order as [`HexShape::iter`].
+    pub coords: Vec<Axial>,
+}
+
+/// The outcome of moving a shape along a path.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct PathMove {
+    /// Every pose the shape passed through before it was blocked, in order.
+    pub poses: Vec<Pose>,
+    /// The step the shape was first blocked at along with the coordinates that blocked it, sorted. None if the
+    /// whole path is clear.
+    pub collision: Option<(usize, Vec<Axial>)>,
+}
+
+impl PathMove {
+    /// Check if the shape reached the end of the path.
+    pub fn is_clear(&self) -> bool {
+        self.collision.is_none()
+    }
+}
+
+// The rotation that points the local q axis along a unit move, keeping the reflection of a transform.
+fn facing_rotation(reflected: bool, direction: Axial) -> Option<i32> {
+    (0..6).find(|rotation| {
+        let transform = Transform {
+            rotation: *rotation,
+            reflected,
+            ..Transform::default()
+        };
+        axial!(1, 0).apply_transform(transform) == direction
+    })
+}
+
+/// Path movement.
+impl<T: Clone, S: ShapeStorage<T>> HexShape<T, S> {
+    /// Move the shape along a path, stopping where it is first blocked.
+    ///
+    /// `pivot` is a tile in shape local space, it is placed on each coordinate of the path and the shape turns
+    /// about it. The shape starts on the first coordinate with the rotation and reflection of its transform.
+    /// Steps further than a tile apart are walked along [`Axial::line`] so nothing is jumped over, and with
+    /// [`Facing::Forward`] the shape turns in place 60 degrees at a time, the shorter way, before each move.
+    ///
+    /// Each pose is legal when `fits` accepts every tile, it is given the coordinate, the collection's data
+    /// there and the shape's tile. The poses up to the first illegal one are returned, see [`PathMove`].
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::ReadCollection;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::movement::Facing;
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// struct Level {
+    ///     walls: HashMap<Axial, bool>,
+    /// }
+    ///
+    /// impl ReadCollection<Axial, bool> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&bool> {
+    ///         self.walls.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let level = Level { walls: HashMap::from([(axial!(4, 1), true)]) };
+    ///
+    /// // A monster two tiles wide walking along q, its second tile hits the wall.
+    /// let monster = HexShape::make_line(1, 1, false, |_| ());
+    /// let path: Vec<Axial> = axial!(0, 0).line(axial!(6, 0));
+    /// let moved = monster.follow_path(&level, axial!(0, 0), path, Facing::Fixed, |_, wall, _| wall.is_none());
+    ///
+    /// assert_eq!(moved.collision, Some((4, vec![axial!(4, 1)])));
+    /// assert_eq!(moved.poses.len(), 4);
+    /// ```
+    pub fn follow_path<U, COL, I, F>(
+        &self,
+        col: &COL,
+        pivot: Axial,
+        path: I,
+        facing: Facing,
+        mut fits: F,
+    ) -> PathMove
+    where
+        COL: ReadCollection<Axial, U>,
+        I: IntoIterator<Item = Axial>,
+        F: FnMut(&Axial, Option<&U>, &T) -> bool,
+    {
+        let mut ret = PathMove::default();
+        let mut transform = self.transform;
+        transform.rotation = transform.rotation.rem_euclid(6);
+
+        // Place the pivot on a coordinate and check the pose, false if it is blocked.
+        let mut pose = |step: usize, position: Axial, mut transform: Transform<Axial>| {
+            transform.translation = Axial::default();
+            transform.translation = position - pivot.apply_transform(transform);
+
+            let mut coords = vec![];
+            let mut blocked = vec![];
+            for (local, tile) in self.iter_local() {
+                let coord = local.apply_transform(transform);
+                if !fits(&coord, col.get(&coord), tile) {
+                    blocked.push(coord);
+                }
+                coords.push(coord);
+            }
+
+            match blocked.is_empty() {
+                true => ret.poses.push(Pose {
+                    step,
+                    transform,
+                    coords,
+                }),
+                false => {
+                    blocked.sort();
+                    blocked.dedup();
+                    ret.collision = Some((step, blocked));
+                }
+            }
+            ret.collision.is_none()
+        };
+
+        let mut position: Option<Axial> = None;
+        'path: for (step, target) in path.into_iter().enumerate() {
+            let Some(mut current) = position else {
+                if !pose(step, target, transform) {
+                    break 'path;
+                }
+                position = Some(target);
+                continue;
+            };
+
+            for next in current.line(target).into_iter().skip(1) {
+                let goal = match facing {
+                    Facing::Fixed => None,
+                    Facing::Forward => facing_rotation(transform.reflected, next - current),
+                };
+
+                // Turn in place towards the move, then take it.
+                if let Some(goal) = goal {
+                    let turn = match (goal - transform.rotation).rem_euclid(6) {
+                        0..=3 => 1,
+                        _ => -1,
+                    };
+                    while transform.rotation != goal {
+                        transform.rotation = (transform.rotation + turn).rem_euclid(6);
+                        if !pose(step, current, transform) {
+                            break 'path;
+                        }
+                    }
+                }
+
+                if !pose(step, next, transform) {
+                    break 'path;
+                }
+                current = next;
+            }
+            position = Some(current);
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::hex::coordinate::HexDirection;
+
+    struct MockCollection {
+        walls: HashMap<Axial, bool>,
+    }
+
+    impl ReadCollection<Axial, bool> for MockCollection {
+        fn get(&self, coord: &Axial) -> Option<&bool> {
+            self.walls.get(coord)
+        }
+    }
+
+    fn open(_: &Axial, wall: Option<&bool>, _: &i32) -> bool {
+        wall.is_none()
+    }
+
+    #[test]
+    fn fixed() {
+        let col = MockCollection {
+            walls: HashMap::new(),
+        };
+        let shape = HexShape::make_line(1, 1, false, |_| 1);
+        let path = axial!(0, 0).line(axial!(3, 0));
+
+        let moved = shape.follow_path(&col, axial!(0, 0), path.clone(), Facing::Fixed, open);
+        assert!(moved.is_clear());
+        assert_eq!(moved.poses.len(), 4);
+        for (pose, position) in moved.poses.iter().zip(&path) {
+            assert_eq!(pose.coords, vec![*position, *position + axial!(0, 1)]);
+            assert_eq!(pose.transform.translation, *position);
+        }
+
+        // The pivot is placed on the path.
+        let moved = shape.follow_path(&col, axial!(0, 1), path.clone(), Facing::Fixed, open);
+        assert_eq!(moved.poses[0].coords, vec![axial!(0, -1), axial!(0, 0)]);
+
+        // Long steps are walked a tile at a time.
+        let moved = shape.follow_path(
+            &col,
+            axial!(0, 0),
+            [axial!(0, 0), axial!(3, 0)],
+            Facing::Fixed,
+            open,
+        );
+        let steps: Vec<usize> = moved.poses.iter().map(|pose| pose.step).collect();
+        assert_eq!(steps, vec![0, 1, 1, 1]);
+
+        let empty = shape.follow_path(&col, axial!(0, 0), [], Facing::Fixed, open);
+        assert_eq!(empty, PathMove::default());
+    }
+
+    #[test]
+    fn collision() {
+        let col = MockCollection {
+            walls: HashMap::from([(axial!(2, 1), true), (axial!(5, 0), true)]),
+        };
+        let shape = HexShape::make_line(1, 1, false, |_| 1);
+        let path = axial!(0, 0).line(axial!(6, 0));
+
+        // The lower tile hits the first wall even though the path is clear.
+        let moved = shape.follow_path(&col, axial!(0, 0), path.clone(), Facing::Fixed, open);
+        assert_eq!(moved.collision, Some((2, vec![axial!(2, 1)])));
+        assert_eq!(
+            moved.poses.last().unwrap().transform.translation,
+            axial!(1, 0)
+        );
+
+        // Blocked where it starts.
+        let moved = shape.follow_path(&col, axial!(0, 0), [axial!(2, 0)], Facing::Fixed, open);
+        assert!(moved.poses.is_empty());
+        assert_eq!(moved.collision, Some((0, vec![axial!(2, 1)])));
+
+        // A jump over the wall is still blocked.
+        let moved = shape.follow_path(
+            &col,
+            axial!(0, 0),
+            [axial!(3, 0), axial!(7, 0)],
+            Facing::Fixed,
+            open,
+        );
+        assert_eq!(moved.collision, Some((1, vec![axial!(5, 0)])));
+    }
+
+    #[test]
+    fn forward() {
+        let col = MockCollection {
+            walls: HashMap::new(),
+        };
+
+        // A vehicle two tiles long, its pivot at the back.
+        let vehicle = HexShape::make_line(1, 0, false, |_| 1);
+        let path = [axial!(0, 0), axial!(1, 0), axial!(1, 1), axial!(0, 1)];
+        let moved = vehicle.follow_path(&col, axial!(0, 0), path, Facing::Forward, open);
+        assert!(moved.is_clear());
+
+        // Every move is along the vehicle's front.
+        for pose in moved.poses.iter() {
+            let front = pose.coords[1] - pose.coords[0];
+            assert_eq!(axial!(1, 0).rotate(None, pose.transform.rotation), front);
+        }
+
+        // Turning to the second and third moves take one and two turns.
+        let rotations: Vec<(usize, i32)> = moved
+            .poses
+            .iter()
+            .map(|pose| (pose.step, pose.transform.rotation))
+            .collect();
+        assert_eq!(
+            rotations,
+            vec![(0, 0), (1, 0), (2, 1), (2, 1), (3, 2), (3, 3), (3, 3)]
+        );
+
+        // A reflected shape still faces forward.
+        let mut mirrored = vehicle.clone();
+        mirrored.mirror(crate::hex::coordinate::Axes::Q);
+        let moved = mirrored.follow_path(
+            &col,
+            axial!(0, 0),
+            [axial!(0, 0), axial!(0, 1)],
+            Facing::Forward,
+            open,
+        );
+        let last = moved.poses.last().unwrap();
+        assert!(last.transform.reflected);
+        assert_eq!(
+            axial!(1, 0).apply_transform(Transform {
+                translation: Axial::default(),
+                ..last.transform
+            }),
+            HexDirection::FrontRight.to_movement_vector()
+        );
+    }
+
+    #[test]
+    fn turn_collision() {
+        // A wall beside the vehicle stops it turning.
+        let col = MockCollection {
+            walls: HashMap::from([(axial!(0, 1), true)]),
+        };
+        let vehicle = HexShape::make_line(1, 0, false, |_| 1);
+        let moved = vehicle.follow_path(
+            &col,
+            axial!(0, 0),
+            [axial!(0, 0), axial!(-1, 1)],
+            Facing::Forward,
+            open,
+        );
+        assert_eq!(moved.collision, Some((1, vec![axial!(0, 1)])));
+        assert_eq!(moved.poses.len(), 1);
+    }
+}
@@ -0,0 +1,525 @@
+//! Tile data that turns with its shape.
+//!
+//! Transforming a [`HexShape`] only changes its transform, the tiles are stored in the orientation they were
+//! created with and the shape's methods keep them that way. That includes methods that reset the transform,
+//! like [`HexShape::bake`], the set operations and morphology. Data with a direction, like a conveyor heading
+//! or a unit facing, implements [`Orientable`] and is read and applied through the shape's `*_oriented`
+//! methods, which apply the rotation and reflection of the transform to the data as well. Use
+//! [`HexShape::bake_oriented`] first to run any other method on the data as it faces in the parent space.
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::lib::*;
+
+use crate::core::{tile::Tile, transform::Transform};
+
+use super::{
+    coordinate::{Axes, Axial, HexDirection},
+    vertex::{Vertex, VertexDirection},
+};
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::core::collection::{Collection, ReadCollection};
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use super::{
+    shape::{HexShape, MergePolicy, MergeReport, ScaleSampling},
+    storage::ShapeStorage,
+};
+
+/// Data that can be rotated and reflected.
+///
+/// Rotations and reflections are about the tile holding the data, the same as [`Axial::rotate`] and
+/// [`Axial::reflect`] about the origin. The provided methods leave the data as it is, so data without a
+/// direction only needs an empty impl. Primitive types and strings are implemented this way, while
+/// [`Tile`], [`Option`], [`Vec`] and tuples turn the data they hold.
+///
+/// ```
+/// use gridava::hex::orientable::Orientable;
+///
+/// #[derive(Clone)]
+/// struct Terrain {
+///     height: i32,
+/// }
+///
+/// impl Orientable for Terrain {}
+/// ```
+pub trait Orientable: Clone {
+    /// Rotate the data.
+    ///
+    /// `rot_dir`: positive denotes CW, negative CCW, magnitude denotes how many 60 degree rotations.
+    fn rotate(&self, _rot_dir: i32) -> Self {
+        self.clone()
+    }
+
+    /// Reflect the data across an axis.
+    fn reflect(&self, _axes: Axes) -> Self {
+        self.clone()
+    }
+
+    /// Apply the rotation and reflection of a transform to the data.
+    ///
+    /// The order of applications is reflection across the q axis then rotation. Translation and scale have no
+    /// meaning for data on a single tile.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::core::transform::{Transform, transform, Vector2D, vector2d};
+    /// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+    /// use gridava::hex::orientable::Orientable;
+    ///
+    /// let facing = HexDirection::Front.apply_transform(transform!(axial!(3, 1), 2));
+    /// assert_eq!(facing, HexDirection::BackRight);
+    /// ```
+    fn apply_transform(&self, transform: Transform<Axial>) -> Self {
+        match transform.reflected {
+            true => self.reflect(Axes::Q).rotate(transform.rotation),
+            false => self.rotate(transform.rotation),
+        }
+    }
+}
+
+// Data without a direction.
+macro_rules! impl_orientable {
+    ($($ty:ty),*) => {
+        $(impl Orientable for $ty {})*
+    };
+}
+
+impl_orientable!(
+    (),
+    bool,
+    char,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize
+);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl Orientable for String {}
+
+/// A tile turns its data.
+impl<T: Orientable> Orientable for Tile<T> {
+    fn rotate(&self, rot_dir: i32) -> Self {
+        Tile {
+            data: self.data.rotate(rot_dir),
+        }
+    }
+
+    fn reflect(&self, axes: Axes) -> Self {
+        Tile {
+            data: self.data.reflect(axes),
+        }
+    }
+}
+
+/// Optional data turns when it is present.
+impl<T: Orientable> Orientable for Option<T> {
+    fn rotate(&self, rot_dir: i32) -> Self {
+        self.as_ref().map(|data| data.rotate(rot_dir))
+    }
+
+    fn reflect(&self, axes: Axes) -> Self {
+        self.as_ref().map(|data| data.reflect(axes))
+    }
+}
+
+/// Every element turns.
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<T: Orientable> Orientable for Vec<T> {
+    fn rotate(&self, rot_dir: i32) -> Self {
+        self.iter().map(|data| data.rotate(rot_dir)).collect()
+    }
+
+    fn reflect(&self, axes: Axes) -> Self {
+        self.iter().map(|data| data.reflect(axes)).collect()
+    }
+}
+
+// Every element of a tuple turns.
+macro_rules! impl_orientable_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: Orientable),+> Orientable for ($($name,)+) {
+            fn rotate(&self, rot_dir: i32) -> Self {
+                ($(self.$idx.rotate(rot_dir),)+)
+            }
+
+            fn reflect(&self, axes: Axes) -> Self {
+                ($(self.$idx.reflect(axes),)+)
+            }
+        }
+    };
+}
+
+impl_orientable_tuple!(A 0);
+impl_orientable_tuple!(A 0, B 1);
+impl_orientable_tuple!(A 0, B 1, C 2);
+impl_orientable_tuple!(A 0, B 1, C 2, D 3);
+
+/// A direction turns like its movement vector.
+impl Orientable for HexDirection {
+    fn rotate(&self, rot_dir: i32) -> Self {
+        HexDirection::from(i32::from(*self) + rot_dir)
+    }
+
+    fn reflect(&self, axes: Axes) -> Self {
+        let reflected = self.to_movement_vector().reflect(None, axes);
+        (0..6)
+            .map(HexDirection::from)
+            .find(|dir| dir.to_movement_vector() == reflected)
+            .unwrap()
+    }
+}
+
+/// A direction turns like the vertex it points to on the tile at the origin.
+impl Orientable for VertexDirection {
+    fn rotate(&self, rot_dir: i32) -> Self {
+        VertexDirection::from(i32::from(*self) + rot_dir)
+    }
+
+    fn reflect(&self, axes: Axes) -> Self {
+        let reflected = Vertex::from(*self).reflect(None, axes);
+        (0..6)
+            .map(VertexDirection::from)
+            .find(|dir| Vertex::from(*dir) == reflected)
+            .unwrap()
+    }
+}
+
+/// Reading and applying orientable tiles.
+///
+/// Tiles are stored in the orientation they were created with, these methods apply the rotation and
+/// reflection of the shape's transform to the data along with the coordinates.
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<T: Orientable, S: ShapeStorage<T>> HexShape<T, S> {
+    /// Iterate over the shape's tiles in the parent space, oriented by the shape's transform.
+    ///
+    /// See [`Self::iter`].
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::HexDirection;
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut conveyor = HexShape::make_line(2, 0, false, |_| HexDirection::Front);
+    /// conveyor.rotate(None, 1);
+    ///
+    /// // The belt runs along r and so does every tile.
+    /// for (coord, heading) in conveyor.iter_oriented() {
+    ///     assert_eq!(heading, HexDirection::FrontRight);
+    /// }
+    /// ```
+    pub fn iter_oriented(&self) -> impl Iterator<Item = (Axial, T)> + '_ {
+        let transform = self.transform;
+        self.iter()
+            .map(move |(coord, tile)| (coord, tile.apply_transform(transform)))
+    }
+
+    /// Get the tile at a coordinate in the parent space, oriented by the shape's transform.
+    ///
+    /// See [`Self::get`].
+    pub fn get_oriented(&self, coord: Axial) -> Option<T> {
+        self.get(coord)
+            .map(|tile| tile.apply_transform(self.transform))
+    }
+
+    /// Apply the shape's transform to its array and its tiles.
+    ///
+    /// See [`Self::bake`], the data is oriented as well so it keeps its direction in the parent space once
+    /// the rotation is reset.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut conveyor = HexShape::make_line(2, 0, false, |_| HexDirection::Front);
+    /// conveyor.rotate(None, 1);
+    /// conveyor.bake_oriented();
+    ///
+    /// assert_eq!(conveyor.get(axial!(0, 1)), Some(&HexDirection::FrontRight));
+    /// ```
+    pub fn bake_oriented(&mut self) -> &Self {
+        *self = Self::from_tiles(self.iter_oriented().collect::<Vec<_>>());
+        self
+    }
+
+    /// Scale a shape in hex space, orienting the tiles by the rotation and reflection it loses.
+    ///
+    /// See [`Self::scale`].
+    pub fn scale_oriented(
+        self,
+        pivot: Option<Axial>,
+        factor: f32,
+        sampling: ScaleSampling,
+    ) -> Self {
+        let pivot = pivot.unwrap_or(self.transform.translation);
+        let mut baked = self;
+        baked.bake_oriented();
+        baked.scale(Some(pivot), factor, sampling)
+    }
+
+    /// Apply a shape to a collection, orienting the tiles by the shape's transform.
+    ///
+    /// See [`Self::apply_shape`].
+    pub fn apply_oriented<COL: Collection<Axial, T>>(&self, col: &mut COL) {
+        self.iter_oriented().for_each(|(coord, value)| {
+            col.set(coord, value);
+        });
+    }
+
+    /// Apply a shape to a collection with a merge policy, orienting the tiles by the shape's transform.
+    ///
+    /// See [`Self::apply_shape_with`], the combine closure is given the oriented tile.
+    pub fn apply_oriented_with<COL>(&self, col: &mut COL, policy: MergePolicy<T>) -> MergeReport
+    where
+        COL: Collection<Axial, T> + ReadCollection<Axial, T>,
+    {
+        let mut oriented = self.clone();
+        oriented.bake_oriented();
+        oriented.apply_shape_with(col, policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{axial, core::transform::Vector2D, transform, vector2d};
+
+    #[test]
+    fn hex_direction() {
+        assert_eq!(HexDirection::Front.rotate(1), HexDirection::FrontRight);
+        assert_eq!(HexDirection::Front.rotate(-1), HexDirection::FrontLeft);
+        assert_eq!(HexDirection::BackLeft.rotate(8), HexDirection::Front);
+
+        // Directions turn with their movement vectors.
+        for idx in 0..6 {
+            let dir = HexDirection::from(idx);
+            let vector = dir.to_movement_vector();
+            for rot_dir in -6..6 {
+                assert_eq!(
+                    dir.rotate(rot_dir).to_movement_vector(),
+                    vector.rotate(None, rot_dir)
+                );
+            }
+            for axes in [Axes::Q, Axes::R, Axes::S] {
+                assert_eq!(
+                    dir.reflect(axes).to_movement_vector(),
+                    vector.reflect(None, axes)
+                );
+                assert_eq!(dir.reflect(axes).reflect(axes), dir);
+            }
+        }
+
+        assert_eq!(
+            HexDirection::Front.reflect(Axes::Q),
+            HexDirection::FrontLeft
+        );
+    }
+
+    #[test]
+    fn vertex_direction() {
+        assert_eq!(VertexDirection::Up.rotate(2), VertexDirection::DownRight);
+        assert_eq!(VertexDirection::Up.rotate(-1), VertexDirection::UpLeft);
+
+        for idx in 0..6 {
+            let dir = VertexDirection::from(idx);
+            let vertex = Vertex::from(dir);
+            for rot_dir in -6..6 {
+                assert_eq!(
+                    Vertex::from(dir.rotate(rot_dir)),
+                    vertex.rotate(None, rot_dir)
+                );
+            }
+            for axes in [Axes::Q, Axes::R, Axes::S] {
+                assert_eq!(Vertex::from(dir.reflect(axes)), vertex.reflect(None, axes));
+            }
+        }
+
+        // Reflecting then rotating matches a reflected transform.
+        let transform = transform!(axial!(0, 0), 2, vector2d!(1.0), true);
+        assert_eq!(
+            Vertex::from(VertexDirection::UpRight.apply_transform(transform)),
+            Vertex::from(VertexDirection::UpRight).apply_transform(transform)
+        );
+    }
+
+    #[test]
+    fn unoriented() {
+        let transform = transform!(axial!(0, 0), 2, vector2d!(1.0), true);
+        assert_eq!(7.apply_transform(transform), 7);
+        assert_eq!('a'.apply_transform(transform), 'a');
+        assert_eq!(
+            Some(HexDirection::Front).apply_transform(transform),
+            Some(HexDirection::Front.apply_transform(transform))
+        );
+        assert_eq!(None::<HexDirection>.rotate(1), None);
+        assert_eq!(String::from("a").apply_transform(transform), "a");
+    }
+
+    #[test]
+    fn forwarded() {
+        assert_eq!(
+            Tile {
+                data: HexDirection::Front
+            }
+            .rotate(1),
+            Tile {
+                data: HexDirection::FrontRight
+            }
+        );
+        assert_eq!(
+            (HexDirection::Front, 7, VertexDirection::Up).rotate(1),
+            (HexDirection::FrontRight, 7, VertexDirection::UpRight)
+        );
+        assert_eq!(
+            vec![HexDirection::Front, HexDirection::Back].reflect(Axes::Q),
+            vec![HexDirection::FrontLeft, HexDirection::BackRight]
+        );
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn shape() {
+        use crate::core::collection::{Collection, ReadCollection};
+        use crate::hex::shape::{MergePolicy, ScaleSampling};
+        use std::collections::HashMap;
+
+        struct MockCollection {
+            tiles: HashMap<Axial, HexDirection>,
+        }
+
+        impl Collection<Axial, HexDirection> for MockCollection {
+            fn set(&mut self, coord: Axial, data: HexDirection) {
+                self.tiles.insert(coord, data);
+            }
+        }
+
+        impl ReadCollection<Axial, HexDirection> for MockCollection {
+            fn get(&self, coord: &Axial) -> Option<&HexDirection> {
+                self.tiles.get(coord)
+            }
+        }
+
+        // Each tile of a conveyor points to the next one.
+        let points_along = |tiles: &[(Axial, HexDirection)]| {
+            tiles
+                .windows(2)
+                .all(|pair| pair[0].0 + pair[0].1.to_movement_vector() == pair[1].0)
+        };
+
+        let mut conveyor = HexShape::make_line(2, 0, false, |_| HexDirection::Front);
+        conveyor.rotate(None, 2);
+        conveyor.translate(axial!(3, 3));
+        let oriented: Vec<(Axial, HexDirection)> = conveyor.iter_oriented().collect();
+        assert!(points_along(&oriented));
+        assert_eq!(
+            conveyor.get_oriented(axial!(2, 4)),
+            Some(HexDirection::BackRight)
+        );
+        assert_eq!(conveyor.get(axial!(2, 4)), Some(&HexDirection::Front));
+
+        // Mirroring flips the data as well.
+        let mut mirrored = conveyor.clone();
+        mirrored.mirror(Axes::R);
+        let oriented: Vec<(Axial, HexDirection)> = mirrored.iter_oriented().collect();
+        assert!(points_along(&oriented));
+
+        // Baking keeps the stored data unless it is oriented.
+        let mut stored = mirrored.clone();
+        stored.bake();
+        assert!(stored.iter().all(|(_, dir)| *dir == HexDirection::Front));
+        mirrored.bake_oriented();
+        assert_eq!(mirrored.transform.rotation, 0);
+        assert!(!mirrored.transform.reflected);
+        let baked: Vec<(Axial, HexDirection)> = mirrored
+            .iter()
+            .map(|(coord, tile)| (coord, *tile))
+            .collect();
+        assert_eq!(baked, oriented);
+
+        // So does scaling and the set operations.
+        let scaled = conveyor.clone().scale(None, 2.0, ScaleSampling::Nearest);
+        assert!(scaled.iter().all(|(_, dir)| *dir == HexDirection::Front));
+        let scaled = conveyor
+            .clone()
+            .scale_oriented(None, 2.0, ScaleSampling::Nearest);
+        assert!(scaled
+            .iter()
+            .all(|(_, dir)| *dir == HexDirection::BackRight));
+        let union = conveyor.union(&HexShape::new(None, None), |a, _| *a);
+        assert_eq!(union.get(axial!(2, 4)), Some(&HexDirection::Front));
+        let mut baked = conveyor.clone();
+        baked.bake_oriented();
+        let union = baked.union(&HexShape::new(None, None), |a, _| *a);
+        assert_eq!(
+            union.get(axial!(2, 4)),
+            conveyor.get_oriented(axial!(2, 4)).as_ref()
+        );
+
+        // Applied tiles are oriented through the oriented variants.
+        let mut col = MockCollection {
+            tiles: HashMap::new(),
+        };
+        conveyor.apply_shape(&mut col);
+        assert!(col.tiles.values().all(|dir| *dir == HexDirection::Front));
+        conveyor.apply_oriented(&mut col);
+        assert_eq!(col.tiles.len(), 3);
+        assert!(col
+            .tiles
+            .values()
+            .all(|dir| *dir == HexDirection::BackRight));
+
+        let mut col = MockCollection {
+            tiles: HashMap::from([(axial!(3, 3), HexDirection::Back)]),
+        };
+        let report = conveyor.apply_oriented_with(
+            &mut col,
+            MergePolicy::Combine(Box::new(|existing, incoming| {
+                assert_eq!(*incoming, HexDirection::BackRight);
+                *existing
+            })),
+        );
+        assert_eq!(report.conflicts, vec![axial!(3, 3)]);
+        assert_eq!(col.tiles[&axial!(3, 3)], HexDirection::Back);
+        assert_eq!(col.tiles[&axial!(1, 5)], HexDirection::BackRight);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn stored() {
+        let mut conveyor = HexShape::make_line(2, 0, false, |_| HexDirection::Front);
+        conveyor.rotate(None, 1);
+
+        // Morphology and canonical forms keep the stored data.
+        let grown = conveyor.dilate(1, |_| HexDirection::Back);
+        assert_eq!(grown.transform.rotation, 0);
+        assert!(conveyor
+            .iter()
+            .all(|(coord, _)| grown.get(coord) == Some(&HexDirection::Front)));
+        assert!(conveyor
+            .canonical(false)
+            .iter()
+            .all(|(_, dir)| *dir == HexDirection::Front));
+
+        // Baking the orientation first carries it through.
+        let mut baked = conveyor.clone();
+        baked.bake_oriented();
+        let grown = baked.dilate(1, |_| HexDirection::Back);
+        assert!(conveyor
+            .iter()
+            .all(|(coord, _)| grown.get(coord) == Some(&HexDirection::FrontRight)));
+        assert!(baked
+            .canonical(false)
+            .iter()
+            .all(|(_, dir)| *dir == HexDirection::FrontRight));
+    }
+}
@@ -0,0 +1,435 @@
+//! Searching for where a shape fits in, or matches, a collection.
+
+use crate::lib::*;
+
+use crate::core::{collection::ReadCollection, transform::Transform};
+
+use super::{coordinate::Axial, shape::HexShape, storage::ShapeStorage, symmetry};
+
+/// A legal position of a shape.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Placement {
+    /// The rotation, reflection and translation applied to the shape's tiles in the parent space.
+    pub transform: Transform<Axial>,
+    /// The coordinates covered by the placed shape, in the same order as [`HexShape::iter`].
+    pub coords: Vec<Axial>,
+}
+
+/// Placement search.
+impl<T: Clone, S: ShapeStorage<T>> HexShape<T, S> {
+    /// Find every legal placement of the shape inside a region.
+    ///
+    /// A placement is legal when every tile of the placed shape lands inside `region` and `fits` accepts it.
+    /// `fits` is given the coordinate, the collection's data there and the shape's tile. Every rotation is
+    /// tried, along with reflections if `reflections` is set, but orientations that give the same tiles and
+    /// data because of the shape's symmetry are only tried once, see [`Self::orientations`].
+    ///
+    /// Placements are found lazily, ordered by orientation then by the region's order.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::ReadCollection;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// struct Level {
+    ///     buildings: HashMap<Axial, u32>,
+    /// }
+    ///
+    /// impl ReadCollection<Axial, u32> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&u32> {
+    ///         self.buildings.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let level = Level { buildings: HashMap::from([(axial!(0, 0), 1)]) };
+    /// let building = HexShape::make_line(1, 0, false, |_| 2);
+    ///
+    /// // Every spot in a small hexagon that is not already built on.
+    /// let placements: Vec<_> = building
+    ///     .placements(&level, axial!(0, 0).range(1), false, |_, existing, _| existing.is_none())
+    ///     .collect();
+    ///
+    /// // Each of the 6 outer tiles has 2 free neighbors in the region, each pair counted once.
+    /// assert_eq!(placements.len(), 6);
+    /// ```
+    pub fn placements<'a, U, COL, I, F>(
+        &self,
+        col: &'a COL,
+        region: I,
+        reflections: bool,
+        mut fits: F,
+    ) -> impl Iterator<Item = Placement> + 'a
+    where
+        T: PartialEq + 'a,
+        U: 'a,
+        COL: ReadCollection<Axial, U>,
+        I: IntoIterator<Item = Axial>,
+        F: FnMut(&Axial, Option<&U>, &T) -> bool + 'a,
+    {
+        let anchors: Vec<Axial> = region.into_iter().collect();
+        let region: BTreeSet<Axial> = anchors.iter().copied().collect();
+
+        // The shape's tiles in each distinct orientation, compared with their data.
+        let mut orientations: Vec<Transform<Axial>> = vec![];
+        let mut oriented: Vec<Vec<(Axial, T)>> = vec![];
+        let mut seen: Vec<Vec<(Axial, T)>> = vec![];
+        for orientation in symmetry::orientations(reflections) {
+            let tiles: Vec<(Axial, T)> = self
+                .iter()
+                .map(|(coord, tile)| (coord.apply_transform(orientation), tile.clone()))
+                .collect();
+
+            let mut normalized = tiles.clone();
+            normalized.sort_by_key(|(coord, _)| *coord);
+            let first = normalized
+                .first()
+                .map(|(coord, _)| *coord)
+                .unwrap_or_default();
+            normalized.iter_mut().for_each(|(coord, _)| *coord -= first);
+
+            if !seen.contains(&normalized) {
+                seen.push(normalized);
+                orientations.push(orientation);
+                oriented.push(tiles);
+            }
+        }
+
+        // Every placement puts the first tile of the oriented shape on a coordinate of the region.
+        let count = anchors.len();
+        (0..oriented.len())
+            .flat_map(move |idx| (0..count).map(move |anchor| (idx, anchor)))
+            .filter_map(move |(idx, anchor)| {
+                let tiles = &oriented[idx];
+                let offset = anchors[anchor] - tiles.first()?.0;
+
+                let coords: Vec<Axial> = tiles.iter().map(|(coord, _)| *coord + offset).collect();
+                let legal = coords.iter().zip(tiles).all(|(coord, (_, tile))| {
+                    region.contains(coord) && fits(coord, col.get(coord), tile)
+                });
+
+                legal.then(|| {
+                    let mut transform = orientations[idx];
+                    transform.translation = offset;
+                    Placement { transform, coords }
+                })
+            })
+    }
+
+    /// Find every legal placement of the shape inside a region and rank them by a score.
+    ///
+    /// Placements are sorted from the highest score to the lowest, placements with equal scores keep the
+    /// order of [`Self::placements`].
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::ReadCollection;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// struct Level {
+    ///     resources: HashMap<Axial, u32>,
+    /// }
+    ///
+    /// impl ReadCollection<Axial, u32> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&u32> {
+    ///         self.resources.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let level = Level { resources: HashMap::from([(axial!(2, 0), 5), (axial!(0, 2), 3)]) };
+    /// let building = HexShape::make_line(1, 0, false, |_| ());
+    ///
+    /// // Cover as many resources as possible.
+    /// let ranked = building.ranked_placements(
+    ///     &level,
+    ///     axial!(0, 0).range(3),
+    ///     true,
+    ///     |_, _, _| true,
+    ///     |placement| placement.coords.iter().filter_map(|coord| level.get(coord)).sum::<u32>(),
+    /// );
+    /// assert_eq!(ranked[0].1, 5);
+    /// ```
+    pub fn ranked_placements<U, COL, I, F, K, FK>(
+        &self,
+        col: &COL,
+        region: I,
+        reflections: bool,
+        fits: F,
+        mut score: FK,
+    ) -> Vec<(Placement, K)>
+    where
+        T: PartialEq,
+        COL: ReadCollection<Axial, U>,
+        I: IntoIterator<Item = Axial>,
+        F: FnMut(&Axial, Option<&U>, &T) -> bool,
+        K: Ord,
+        FK: FnMut(&Placement) -> K,
+    {
+        let mut ranked: Vec<(Placement, K)> = self
+            .placements(col, region, reflections, fits)
+            .map(|placement| {
+                let score = score(&placement);
+                (placement, score)
+            })
+            .collect();
+
+        ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+        ranked
+    }
+
+    /// Find every occurrence of a pattern in a region of a collection.
+    ///
+    /// The shape is the pattern, it matches where every one of its tiles lands inside `region` on a coordinate
+    /// the collection has data for and `predicate` accepts the pattern's tile and the collection's data.
+    /// Empty cells of the pattern are wildcards and match anything. Every rotation is tried, along with
+    /// reflections if `reflections` is set, and each occurrence is only found once even when the pattern is
+    /// symmetric.
+    ///
+    /// Returns the transform that takes the pattern's tiles in the parent space onto each occurrence.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::ReadCollection;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// struct Level {
+    ///     gems: HashMap<Axial, char>,
+    /// }
+    ///
+    /// impl ReadCollection<Axial, char> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&char> {
+    ///         self.gems.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let mut level = Level { gems: HashMap::new() };
+    /// for coord in axial!(0, 0).range(2) {
+    ///     level.gems.insert(coord, 'b');
+    /// }
+    /// for coord in [axial!(-1, 0), axial!(0, 0), axial!(1, 0)] {
+    ///     level.gems.insert(coord, 'r');
+    /// }
+    ///
+    /// // Three of a kind in a row.
+    /// let pattern = HexShape::make_line(2, 0, false, |_| 'r');
+    /// let matches: Vec<_> = pattern
+    ///     .find_matches(&level, axial!(0, 0).range(2), true, |gem, other| gem == other)
+    ///     .collect();
+    /// assert_eq!(matches.len(), 1);
+    /// ```
+    pub fn find_matches<'a, U, COL, I, F>(
+        &self,
+        col: &'a COL,
+        region: I,
+        reflections: bool,
+        mut predicate: F,
+    ) -> impl Iterator<Item = Transform<Axial>> + 'a
+    where
+        T: PartialEq + 'a,
+        U: 'a,
+        COL: ReadCollection<Axial, U>,
+        I: IntoIterator<Item = Axial>,
+        F: FnMut(&T, &U) -> bool + 'a,
+    {
+        self.placements(col, region, reflections, move |_, existing, tile| {
+            existing.is_some_and(|existing| predicate(tile, existing))
+        })
+        .map(|placement| placement.transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axial;
+    use std::collections::HashMap;
+
+    struct MockCollection {
+        tiles: HashMap<Axial, i32>,
+    }
+
+    impl ReadCollection<Axial, i32> for MockCollection {
+        fn get(&self, coord: &Axial) -> Option<&i32> {
+            self.tiles.get(coord)
+        }
+    }
+
+    fn empty() -> MockCollection {
+        MockCollection {
+            tiles: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn placements() {
+        let col = empty();
+
+        // A single tile fits everywhere in the region.
+        let tile = HexShape::make_line(0, 0, false, |_| 1);
+        let region = axial!(0, 0).range(2);
+        assert_eq!(
+            tile.placements(&col, region.clone(), true, |_, _, _| true)
+                .count(),
+            region.len()
+        );
+
+        // A line of 2 in a hexagon of size 1, one placement per internal edge.
+        let line = HexShape::make_line(1, 0, false, |_| 1);
+        let placements: Vec<Placement> = line
+            .placements(&col, axial!(0, 0).range(1), true, |_, _, _| true)
+            .collect();
+        assert_eq!(placements.len(), 12);
+
+        // Every placement is inside the region and matches its transform.
+        for placement in &placements {
+            assert!(placement
+                .coords
+                .iter()
+                .all(|coord| coord.distance(axial!(0, 0)) <= 1));
+            let coords: Vec<Axial> = line
+                .iter()
+                .map(|(coord, _)| coord.apply_transform(placement.transform))
+                .collect();
+            assert_eq!(coords, placement.coords);
+        }
+
+        // With different data on each end both directions of the line are distinct.
+        let mut pair = line.clone();
+        pair.get_hexes_mut()[[1, 0]] = Some(2);
+        assert_eq!(
+            pair.placements(&col, axial!(0, 0).range(1), true, |_, _, _| true)
+                .count(),
+            24
+        );
+
+        // Nothing fits in a region that is too small.
+        assert_eq!(
+            line.placements(&col, [axial!(0, 0)], true, |_, _, _| true)
+                .count(),
+            0
+        );
+        assert_eq!(
+            HexShape::<i32>::new(None, None)
+                .placements(&col, region, true, |_, _, _| true)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn placements_occupied() {
+        let col = MockCollection {
+            tiles: HashMap::from([(axial!(0, 0), 1), (axial!(1, 0), 2)]),
+        };
+
+        // The center is occupied, so only the edges around the outside that miss (1, 0) are left.
+        let line = HexShape::make_line(1, 0, false, |_| 1);
+        let free: Vec<Placement> = line
+            .placements(&col, axial!(0, 0).range(1), false, |_, existing, _| {
+                existing.is_none()
+            })
+            .collect();
+        assert_eq!(free.len(), 4);
+        assert!(free.iter().all(|placement| !placement
+            .coords
+            .iter()
+            .any(|coord| col.tiles.contains_key(coord))));
+
+        // The predicate can compare the collection against the shape's tiles.
+        let matching = line
+            .placements(&col, axial!(0, 0).range(2), true, |_, existing, tile| {
+                existing.map_or(true, |existing| existing == tile)
+            })
+            .count();
+        assert!(matching > free.len());
+
+        // A shape with no symmetry has twice the placements with reflections.
+        let mut chiral = HexShape::make_line(2, 0, true, |_| 1);
+        chiral.get_hexes_mut()[[0, 1]] = Some(1);
+        let count = |reflections| {
+            chiral
+                .placements(&empty(), axial!(0, 0).range(2), reflections, |_, _, _| true)
+                .count()
+        };
+        assert!(count(false) > 0);
+        assert_eq!(count(true), 2 * count(false));
+    }
+
+    #[test]
+    fn ranked_placements() {
+        let col = MockCollection {
+            tiles: HashMap::from([(axial!(1, 0), 4), (axial!(-1, 0), 2)]),
+        };
+
+        let line = HexShape::make_line(1, 0, false, |_| 1);
+        let ranked = line.ranked_placements(
+            &col,
+            axial!(0, 0).range(1),
+            true,
+            |_, _, _| true,
+            |placement| {
+                placement
+                    .coords
+                    .iter()
+                    .filter_map(|coord| col.get(coord))
+                    .sum::<i32>()
+            },
+        );
+
+        assert_eq!(ranked.len(), 12);
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert_eq!(ranked[0].1, 4);
+        assert!(ranked[0].0.coords.contains(&axial!(1, 0)));
+        assert_eq!(ranked.last().unwrap().1, 0);
+    }
+
+    #[test]
+    fn find_matches() {
+        // 1s around an empty center with a 2 at (1, 0).
+        let mut col = empty();
+        for coord in axial!(0, 0).range(1) {
+            if coord != axial!(0, 0) {
+                col.tiles.insert(coord, 1);
+            }
+        }
+        col.tiles.insert(axial!(1, 0), 2);
+        let region = axial!(0, 0).range(1);
+
+        // Three tiles bent around the center, only the one with its middle on the 2 matches.
+        let pattern =
+            HexShape::from_iter([(axial!(1, -1), 0), (axial!(1, 0), 2), (axial!(0, 1), 0)]);
+        let matches: Vec<Transform<Axial>> = pattern
+            .find_matches(&col, region.clone(), true, |a, b| *a == 0 || a == b)
+            .collect();
+        assert_eq!(matches.len(), 1);
+        for transform in &matches {
+            assert!(pattern
+                .iter()
+                .all(|(coord, _)| col.get(&coord.apply_transform(*transform)).is_some()));
+            assert_eq!(col.get(&axial!(1, 0).apply_transform(*transform)), Some(&2));
+        }
+
+        // The empty center of the pattern is a wildcard, so it matches across the missing center.
+        let mut line = HexShape::make_line(2, 0, false, |_| 1);
+        line.get_hexes_mut()[[1, 0]] = None;
+        let any = |_: &i32, _: &i32| true;
+        assert_eq!(
+            line.find_matches(&col, region.clone(), true, any).count(),
+            3
+        );
+
+        // A pattern with symmetric tiles but not data is tried in every orientation of its data.
+        let mut pair = HexShape::make_line(1, 0, false, |_| 1);
+        pair.get_hexes_mut()[[1, 0]] = Some(2);
+        let pairs = pair
+            .find_matches(&col, region.clone(), true, |a, b| a == b)
+            .count();
+        assert_eq!(pairs, 2);
+
+        // Coordinates without data never match.
+        let tile = HexShape::make_line(0, 0, false, |_| 1);
+        assert_eq!(tile.find_matches(&col, region, true, any).count(), 6);
+    }
+}
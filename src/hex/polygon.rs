@@ -0,0 +1,319 @@
+//! Shapes filled from polygons.
+//!
+//! [`HexShape::make_shape`] fills the hex aligned bounds of its points so it can only make convex shapes. The
+//! constructors here fill any simple polygon, outlined by tiles, vertices or points in world space.
+
+use crate::lib::*;
+
+use super::{bounds::HexBounds, coordinate::Axial, shape::HexShape, vertex::Vertex};
+
+#[cfg(feature = "std")]
+use super::grid::WSConverter;
+
+// Check if a point is inside closed rings of points by the even-odd rule.
+//
+// Crossings are counted on a ray towards positive x, an edge counts when one end is above the point and the
+// other is not, so a point exactly on an edge may fall either way.
+fn contains<P>(rings: &[&[(P, P)]], point: (P, P)) -> bool
+where
+    P: Copy + PartialOrd + Sub<Output = P> + Mul<Output = P>,
+{
+    let (px, py) = point;
+    let mut inside = false;
+
+    for ring in rings {
+        for idx in 0..ring.len() {
+            let (ax, ay) = ring[idx];
+            let (bx, by) = ring[(idx + 1) % ring.len()];
+            if (ay > py) == (by > py) {
+                continue;
+            }
+
+            // The point is left of where the edge crosses its row.
+            let crosses = match by > ay {
+                true => (px - ax) * (by - ay) < (py - ay) * (bx - ax),
+                false => (px - ax) * (by - ay) > (py - ay) * (bx - ax),
+            };
+            if crosses {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Polygon constructors.
+///
+/// Like the other constructors the shape is moved so its least q and r are 0 with an identity transform, and
+/// `square_bb` and `constructor` are as in [`HexShape::make_shape`].
+impl<T: Clone> HexShape<T> {
+    /// Create a shape by filling a polygon outlined by tiles.
+    ///
+    /// The outline is an ordered list of corner tiles, each joined to the next by [`Axial::line`] and the last
+    /// joined back to the first. The shape is the tiles on the outline and every tile whose center is inside it,
+    /// so the outline may be concave. Self intersecting outlines are filled by the even-odd rule.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// /// A chevron, make_shape would fill in the notch.
+    /// let outline = [axial!(0, 0), axial!(4, 0), axial!(4, 4), axial!(2, 1), axial!(0, 4)];
+    /// let my_shape = HexShape::make_polygon(&outline, false, |_| 1);
+    /// ```
+    pub fn make_polygon<F>(outline: &[Axial], square_bb: bool, constructor: F) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        let Some(bounds) = HexBounds::from_points(outline.iter().copied()) else {
+            return HexShape::new(None, None);
+        };
+
+        let ring: Vec<(i64, i64)> = outline
+            .iter()
+            .map(|coord| Vertex::scaled_center(*coord))
+            .collect();
+        let mut coords: Vec<Axial> = bounds
+            .hexes()
+            .into_iter()
+            .filter(|coord| contains(&[&ring], Vertex::scaled_center(*coord)))
+            .collect();
+
+        for idx in 0..outline.len() {
+            coords.extend(outline[idx].line(outline[(idx + 1) % outline.len()]));
+        }
+
+        Self::make_local(coords, square_bb, constructor)
+    }
+
+    /// Create a shape by filling a closed loop of vertices.
+    ///
+    /// Each vertex is joined to the next by [`Vertex::line`] and the last joined back to the first, so the loop
+    /// runs along the edges of the grid. The shape is exactly the tiles the loop encloses, a loop from
+    /// [`outline`](super::algorithms::outline) gives back the tiles it was found from. Self intersecting loops
+    /// are filled by the even-odd rule.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::Axial;
+    /// use gridava::hex::shape::HexShape;
+    /// use gridava::hex::vertex::{Vertex, VertexDirection};
+    ///
+    /// /// The vertices around the tile (0, 0).
+    /// let vertices: Vec<Vertex> = (0..6).map(|dir| VertexDirection::from(dir).into()).collect();
+    /// let my_shape = HexShape::make_vertex_loop(&vertices, false, |_| 1);
+    ///
+    /// assert_eq!(my_shape.area(), 1);
+    /// ```
+    pub fn make_vertex_loop<F>(vertices: &[Vertex], square_bb: bool, constructor: F) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        let path: Vec<Vertex> = (0..vertices.len())
+            .flat_map(|idx| {
+                let mut line = vertices[idx].line(vertices[(idx + 1) % vertices.len()]);
+                line.pop();
+                line
+            })
+            .collect();
+
+        let Some(bounds) =
+            HexBounds::from_points(path.iter().flat_map(|vertex| vertex.adjacent_hexes()))
+        else {
+            return HexShape::new(None, None);
+        };
+
+        // Vertex positions are the sum of three hex centers, scale the centers to match. A center is never on
+        // an edge of the grid so every tile is clearly inside or outside.
+        let ring: Vec<(i64, i64)> = path.iter().map(|vertex| vertex.scaled_position()).collect();
+        let coords = bounds.hexes().into_iter().filter(|coord| {
+            let (x, y) = Vertex::scaled_center(*coord);
+            contains(&[&ring], (3 * x, 3 * y))
+        });
+
+        Self::make_local(coords, square_bb, constructor)
+    }
+
+    /// Create a shape by rasterising a polygon in world space.
+    ///
+    /// The outline and holes are closed rings of world space points, see [`WSConverter`]. The shape is every
+    /// tile whose center is inside the outline and outside of the holes, a center exactly on an edge may fall
+    /// either way. Rings are combined by the even-odd rule.
+    ///
+    /// ```
+    /// use gridava::hex::grid::{WSConverter, HexOrientation};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let converter = WSConverter { size: 10.0, orientation: HexOrientation::PointyTop };
+    ///
+    /// /// A square with a square hole in its middle.
+    /// let outline = [(0.0, 0.0), (200.0, 0.0), (200.0, 200.0), (0.0, 200.0)];
+    /// let hole = vec![(50.0, 50.0), (150.0, 50.0), (150.0, 150.0), (50.0, 150.0)];
+    /// let my_shape = HexShape::make_world_polygon(&converter, &outline, &[hole], false, |_| 1);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn make_world_polygon<F>(
+        converter: &WSConverter,
+        outline: &[(f64, f64)],
+        holes: &[Vec<(f64, f64)>],
+        square_bb: bool,
+        constructor: F,
+    ) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        // A point is within the cell of its nearest tile, which is inside the hexagon of that tile's neighbors.
+        let nearest = outline.iter().map(|point| converter.world_to_hex(*point));
+        let Some(bounds) = HexBounds::from_points(nearest.flat_map(|coord| coord.range(1))) else {
+            return HexShape::new(None, None);
+        };
+
+        let mut rings = vec![outline];
+        rings.extend(holes.iter().map(|hole| hole.as_slice()));
+        let coords = bounds
+            .hexes()
+            .into_iter()
+            .filter(|coord| contains(&rings, converter.hex_to_world(*coord)));
+
+        Self::make_local(coords, square_bb, constructor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{axial, hex::algorithms::outline, hex::vertex::VertexDirection};
+
+    #[test]
+    fn make_polygon() {
+        // Hex aligned outlines match the convex constructors.
+        let corners: Vec<Axial> = (0..6).map(|dir| axial!(0, 0).make_vector(2, dir)).collect();
+        assert_eq!(
+            HexShape::make_polygon(&corners, true, |_| 1),
+            HexShape::make_hexagon(2, 0, true, |_| 1)
+        );
+        assert_eq!(
+            HexShape::make_polygon(&[axial!(0, 0), axial!(3, 0), axial!(0, 3)], false, |_| 1),
+            HexShape::make_triangle(3, 0, false, |_| 1)
+        );
+
+        // The notch of a concave outline is left empty.
+        let outline = [
+            axial!(0, 0),
+            axial!(4, 0),
+            axial!(4, 4),
+            axial!(2, 1),
+            axial!(0, 4),
+        ];
+        let shape = HexShape::make_polygon(&outline, false, |_| 1);
+        let convex = HexShape::make_shape(&outline, false, |_| 1);
+        assert!(shape.area() < convex.area());
+        assert_eq!(shape.get(axial!(2, 3)), None);
+        assert_eq!(convex.get(axial!(2, 3)), Some(&1));
+        for coord in outline {
+            assert_eq!(shape.get(coord), Some(&1));
+        }
+
+        // Degenerate outlines are their lines.
+        assert_eq!(
+            HexShape::make_polygon(&[axial!(0, 0)], false, |_| 1).area(),
+            1
+        );
+        assert_eq!(
+            HexShape::make_polygon(&[axial!(0, 0), axial!(3, 0)], false, |_| 1),
+            HexShape::make_line(3, 0, false, |_| 1)
+        );
+        assert_eq!(HexShape::<i32>::make_polygon(&[], false, |_| 1).area(), 0);
+    }
+
+    #[test]
+    fn make_vertex_loop() {
+        let vertices: Vec<Vertex> = (0..6)
+            .map(|dir| VertexDirection::from(dir).into())
+            .collect();
+        assert_eq!(
+            HexShape::make_vertex_loop(&vertices, false, |_| 1).area(),
+            1
+        );
+
+        // The outline of a concave region fills back to the region.
+        let region: Vec<Axial> = [axial!(1, 0), axial!(2, 0), axial!(2, 1)]
+            .into_iter()
+            .chain(axial!(0, 0).line(axial!(0, 3)))
+            .collect();
+        let border = outline(region.iter().copied());
+        let shape = HexShape::make_vertex_loop(&border.outer[0], false, |_| 1);
+        let expected: HexShape<i32> = region.iter().map(|coord| (*coord, 1)).collect();
+        assert_eq!(shape.get_hexes(), expected.get_hexes());
+
+        // Corners are joined along the edges of the grid. Joining every third vertex of the outline cuts off
+        // the tips at (2, 1) and (0, 3) and closes over the notch at (1, 1).
+        let corners: Vec<Vertex> = border.outer[0].iter().step_by(3).copied().collect();
+        let shape = HexShape::make_vertex_loop(&corners, false, |_| 1);
+        let expected: HexShape<i32> = [
+            axial!(0, 0),
+            axial!(1, 0),
+            axial!(2, 0),
+            axial!(0, 1),
+            axial!(1, 1),
+            axial!(0, 2),
+        ]
+        .into_iter()
+        .map(|coord| (coord, 1))
+        .collect();
+        assert_eq!(shape.get_hexes(), expected.get_hexes());
+
+        assert_eq!(
+            HexShape::<i32>::make_vertex_loop(&[], false, |_| 1).area(),
+            0
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn make_world_polygon() {
+        use crate::hex::grid::HexOrientation;
+
+        let converter = WSConverter {
+            size: 10.0,
+            orientation: HexOrientation::PointyTop,
+        };
+        let square = |min: f64, max: f64| vec![(min, min), (max, min), (max, max), (min, max)];
+        let inside = |coord: &Axial, min: f64, max: f64| {
+            let (x, y) = converter.hex_to_world(*coord);
+            x > min && x < max && y > min && y < max
+        };
+
+        // Every tile with its center inside.
+        let shape =
+            HexShape::make_world_polygon(&converter, &square(1.0, 199.0), &[], false, |_| 1);
+        let expected: HexShape<i32> = axial!(0, 0)
+            .range(30)
+            .into_iter()
+            .filter(|coord| inside(coord, 1.0, 199.0))
+            .map(|coord| (coord, 1))
+            .collect();
+        assert_eq!(shape.get_hexes(), expected.get_hexes());
+
+        // Holes are left empty.
+        let holed = HexShape::make_world_polygon(
+            &converter,
+            &square(1.0, 199.0),
+            &[square(51.0, 149.0)],
+            false,
+            |_| 1,
+        );
+        let expected: HexShape<i32> = axial!(0, 0)
+            .range(30)
+            .into_iter()
+            .filter(|coord| inside(coord, 1.0, 199.0) && !inside(coord, 51.0, 149.0))
+            .map(|coord| (coord, 1))
+            .collect();
+        assert_eq!(holed.get_hexes(), expected.get_hexes());
+
+        assert_eq!(
+            HexShape::<i32>::make_world_polygon(&converter, &[], &[], false, |_| 1).area(),
+            0
+        );
+    }
+}
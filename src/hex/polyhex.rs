@@ -0,0 +1,495 @@
+//! Polyhex enumeration and exact cover tiling.
+
+use crate::lib::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{coordinate::Axial, placement::Placement, shape::HexShape, symmetry::normalize};
+
+/// Which transforms make two polyhexes the same.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum PolyhexKind {
+    /// Only translations, every rotation and reflection of a shape is different.
+    Fixed,
+    /// Translations and rotations, mirror images are different.
+    OneSided,
+    /// Translations, rotations and reflections.
+    Free,
+}
+
+impl PolyhexKind {
+    // The key shared by every polyhex that is the same as these coordinates.
+    fn key(&self, coords: Vec<Axial>) -> Vec<Axial> {
+        let shape: HexShape<()> = coords.into_iter().map(|coord| (coord, ())).collect();
+        match self {
+            PolyhexKind::Fixed => normalize(shape.iter().map(|(coord, _)| coord).collect()).0,
+            PolyhexKind::OneSided => shape.canonical_key(false),
+            PolyhexKind::Free => shape.canonical_key(true),
+        }
+    }
+}
+
+/// Enumerate every polyhex of a size.
+///
+/// A polyhex is a set of `size` tiles connected through their edges. Each polyhex is returned once as its
+/// canonical form, see [`HexShape::canonical`], or with its minimum q and r at 0 for [`PolyhexKind::Fixed`].
+/// Polyhexes are sorted by their tile coordinates and the tiles are filled using the constructor.
+///
+/// The number of polyhexes grows exponentially with the size.
+///
+/// ```
+/// use gridava::hex::polyhex::{polyhexes, PolyhexKind};
+///
+/// // The 7 free tetrahexes.
+/// assert_eq!(polyhexes(4, PolyhexKind::Free, |_| ()).len(), 7);
+/// ```
+pub fn polyhexes<T, F>(size: usize, kind: PolyhexKind, mut constructor: F) -> Vec<HexShape<T>>
+where
+    T: Clone,
+    F: FnMut(Axial) -> T,
+{
+    if size == 0 {
+        return vec![];
+    }
+
+    // Grow every polyhex one tile at a time, every polyhex has a tile that can be removed leaving it connected.
+    let mut level: BTreeSet<Vec<Axial>> = BTreeSet::from([vec![Axial::default()]]);
+    for _ in 1..size {
+        let mut next = BTreeSet::new();
+        for coords in &level {
+            for coord in coords {
+                for neighbor in coord.neighbors() {
+                    if coords.contains(&neighbor) {
+                        continue;
+                    }
+
+                    let mut grown = coords.clone();
+                    grown.push(neighbor);
+                    next.insert(kind.key(grown));
+                }
+            }
+        }
+        level = next;
+    }
+
+    level
+        .into_iter()
+        .map(|coords| {
+            coords
+                .into_iter()
+                .map(|coord| (coord, constructor(coord)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Working state for [`solve_tiling`], a dancing links exact cover matrix.
+///
+/// Node 0 is the root and the next nodes are the column headers, the region's tiles first and then one
+/// column for each piece. Only the tile columns are linked to the root, piece columns are covered once all
+/// of the copies of the piece are used.
+struct DancingLinks {
+    /// Horizontal links.
+    left: Vec<usize>,
+    right: Vec<usize>,
+    /// Vertical links.
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of each node.
+    column: Vec<usize>,
+    /// The row of each node.
+    row: Vec<usize>,
+    /// The number of rows in each column.
+    size: Vec<usize>,
+    /// Number of tile columns.
+    tiles: usize,
+    /// Copies of each piece that still have to be placed, indexed by piece.
+    needed: Vec<usize>,
+    /// Rows of the solution being explored.
+    partial: Vec<usize>,
+    /// Every solution found so far.
+    solutions: Vec<Vec<usize>>,
+    /// The most solutions to find.
+    limit: Option<usize>,
+}
+
+impl DancingLinks {
+    fn new(tiles: usize, needed: Vec<usize>) -> Self {
+        let headers = 1 + tiles + needed.len();
+        let mut links = Self {
+            left: (0..headers).collect(),
+            right: (0..headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec![usize::MAX; headers],
+            size: vec![0; headers],
+            tiles,
+            needed,
+            partial: vec![],
+            solutions: vec![],
+            limit: None,
+        };
+
+        // Link the root and tile columns in a circle.
+        for col in 0..=tiles {
+            links.right[col] = (col + 1) % (tiles + 1);
+            links.left[(col + 1) % (tiles + 1)] = col;
+        }
+        links
+    }
+
+    /// Add a row covering some columns.
+    fn add_row(&mut self, row: usize, columns: &[usize]) {
+        let first = self.left.len();
+        for (idx, col) in columns.iter().enumerate() {
+            let node = first + idx;
+            self.left.push(if idx == 0 {
+                first + columns.len() - 1
+            } else {
+                node - 1
+            });
+            self.right.push(if idx == columns.len() - 1 {
+                first
+            } else {
+                node + 1
+            });
+            self.up.push(self.up[*col]);
+            self.down.push(*col);
+            self.column.push(*col);
+            self.row.push(row);
+
+            let above = self.up[*col];
+            self.down[above] = node;
+            self.up[*col] = node;
+            self.size[*col] += 1;
+        }
+    }
+
+    fn is_piece(&self, col: usize) -> bool {
+        col > self.tiles
+    }
+
+    fn cover(&mut self, col: usize) {
+        let (left, right) = (self.left[col], self.right[col]);
+        self.right[left] = right;
+        self.left[right] = left;
+
+        let mut row = self.down[col];
+        while row != col {
+            let mut node = self.right[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, col: usize) {
+        let mut row = self.up[col];
+        while row != col {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = node;
+                self.up[down] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+
+        let (left, right) = (self.left[col], self.right[col]);
+        self.right[left] = col;
+        self.left[right] = col;
+    }
+
+    /// Use the columns of a row other than the one it was chosen from.
+    fn select(&mut self, row: usize) {
+        let mut node = self.right[row];
+        while node != row {
+            let col = self.column[node];
+            if self.is_piece(col) {
+                let piece = col - self.tiles - 1;
+                self.needed[piece] -= 1;
+                if self.needed[piece] == 0 {
+                    self.cover(col);
+                }
+            } else {
+                self.cover(col);
+            }
+            node = self.right[node];
+        }
+    }
+
+    /// Undo [`Self::select`].
+    fn deselect(&mut self, row: usize) {
+        let mut node = self.left[row];
+        while node != row {
+            let col = self.column[node];
+            if self.is_piece(col) {
+                let piece = col - self.tiles - 1;
+                if self.needed[piece] == 0 {
+                    self.uncover(col);
+                }
+                self.needed[piece] += 1;
+            } else {
+                self.uncover(col);
+            }
+            node = self.left[node];
+        }
+    }
+
+    /// Search for every way to cover the remaining tile columns.
+    fn search(&mut self) {
+        if self
+            .limit
+            .is_some_and(|limit| self.solutions.len() >= limit)
+        {
+            return;
+        }
+
+        if self.right[0] == 0 {
+            if self.needed.iter().all(|needed| *needed == 0) {
+                self.solutions.push(self.partial.clone());
+            }
+            return;
+        }
+
+        // Branch on the tile with the fewest ways to cover it.
+        let mut best = self.right[0];
+        let mut col = self.right[best];
+        while col != 0 {
+            if self.size[col] < self.size[best] {
+                best = col;
+            }
+            col = self.right[col];
+        }
+        if self.size[best] == 0 {
+            return;
+        }
+
+        self.cover(best);
+        let mut node = self.down[best];
+        while node != best {
+            self.partial.push(self.row[node]);
+            self.select(node);
+            self.search();
+            self.deselect(node);
+            self.partial.pop();
+            node = self.down[node];
+        }
+        self.uncover(best);
+    }
+}
+
+/// Find the ways to exactly cover a region with a set of pieces.
+///
+/// Each piece is given with the number of copies of it to place, every copy is used and every tile of the
+/// region is covered by exactly one of them. Pieces may be rotated, and reflected if `reflections` is set.
+/// Copies of a piece are interchangeable so swapping them does not give a new solution.
+///
+/// Each solution lists the index of the piece and where it was placed, the placements are applied to the
+/// pieces' tiles in the parent space. At most `limit` solutions are found, or every solution if it is None.
+///
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::polyhex::solve_tiling;
+/// use gridava::hex::shape::HexShape;
+///
+/// // Tile a hexagon of size 1 with a single tile and three pairs.
+/// let pieces = [
+///     (HexShape::make_line(0, 0, false, |_| ()), 1),
+///     (HexShape::make_line(1, 0, false, |_| ()), 3),
+/// ];
+/// let solutions = solve_tiling(axial!(0, 0).range(1), &pieces, false, None);
+/// assert_eq!(solutions.len(), 20);
+///
+/// // Only the first solution.
+/// let first = solve_tiling(axial!(0, 0).range(1), &pieces, false, Some(1));
+/// assert_eq!(first.len(), 1);
+/// ```
+pub fn solve_tiling<T, I>(
+    region: I,
+    pieces: &[(HexShape<T>, usize)],
+    reflections: bool,
+    limit: Option<usize>,
+) -> Vec<Vec<(usize, Placement)>>
+where
+    T: Clone,
+    I: IntoIterator<Item = Axial>,
+{
+    let region: BTreeSet<Axial> = region.into_iter().collect();
+    let columns: BTreeMap<Axial, usize> = region
+        .iter()
+        .enumerate()
+        .map(|(idx, coord)| (*coord, idx + 1))
+        .collect();
+
+    // Pieces without tiles are always placed.
+    let needed: Vec<usize> = pieces
+        .iter()
+        .map(|(piece, count)| if piece.area() == 0 { 0 } else { *count })
+        .collect();
+
+    let area: usize = pieces
+        .iter()
+        .map(|(piece, count)| piece.area() * count)
+        .sum();
+    if area != region.len() {
+        return vec![];
+    }
+
+    let mut links = DancingLinks::new(region.len(), needed.clone());
+    links.limit = limit;
+
+    // Every placement of every piece that fits in the region.
+    let mut rows: Vec<(usize, Placement)> = vec![];
+    for (idx, (piece, _)) in pieces.iter().enumerate() {
+        if needed[idx] == 0 {
+            continue;
+        }
+
+        for orientation in piece.orientations(reflections) {
+            let tiles: Vec<Axial> = piece
+                .iter()
+                .map(|(coord, _)| coord.apply_transform(orientation))
+                .collect();
+
+            for anchor in &region {
+                let offset = *anchor - tiles[0];
+                let coords: Vec<Axial> = tiles.iter().map(|coord| *coord + offset).collect();
+                let cols: Option<Vec<usize>> = coords
+                    .iter()
+                    .map(|coord| columns.get(coord).copied())
+                    .collect();
+
+                if let Some(mut cols) = cols {
+                    cols.push(region.len() + 1 + idx);
+                    links.add_row(rows.len(), &cols);
+
+                    let mut transform = orientation;
+                    transform.translation = offset;
+                    rows.push((idx, Placement { transform, coords }));
+                }
+            }
+        }
+    }
+
+    links.search();
+    links
+        .solutions
+        .into_iter()
+        .map(|solution| solution.into_iter().map(|row| rows[row].clone()).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{axial, hex::coordinate::Axes};
+
+    #[test]
+    fn polyhexes() {
+        let count = |size, kind| super::polyhexes(size, kind, |_| ()).len();
+
+        assert_eq!(count(0, PolyhexKind::Free), 0);
+        assert_eq!(
+            (1..=5)
+                .map(|size| count(size, PolyhexKind::Fixed))
+                .collect::<Vec<_>>(),
+            vec![1, 3, 11, 44, 186]
+        );
+        assert_eq!(
+            (1..=5)
+                .map(|size| count(size, PolyhexKind::OneSided))
+                .collect::<Vec<_>>(),
+            vec![1, 1, 3, 10, 33]
+        );
+        assert_eq!(
+            (1..=5)
+                .map(|size| count(size, PolyhexKind::Free))
+                .collect::<Vec<_>>(),
+            vec![1, 1, 3, 7, 22]
+        );
+
+        // Results are canonical and filled by the constructor.
+        for shape in super::polyhexes(4, PolyhexKind::Free, |coord| coord.q) {
+            assert_eq!(shape.canonical(true), shape);
+            assert!(shape.iter().all(|(coord, tile)| coord.q == *tile));
+        }
+    }
+
+    #[test]
+    fn solve_tiling() {
+        let single = HexShape::make_line(0, 0, false, |_| 1);
+        let pair = HexShape::make_line(1, 0, false, |_| 2);
+
+        // Singles only tile one way.
+        let region = axial!(0, 0).range(1);
+        assert_eq!(
+            super::solve_tiling(region.clone(), &[(single.clone(), 7)], false, None).len(),
+            1
+        );
+
+        // A single tile and three pairs, 2 ways with the single in the center and 3 for each tile around it.
+        let pieces = [(single.clone(), 1), (pair.clone(), 3)];
+        let solutions = super::solve_tiling(region.clone(), &pieces, false, None);
+        assert_eq!(solutions.len(), 20);
+        for solution in &solutions {
+            let mut covered: Vec<Axial> = solution
+                .iter()
+                .flat_map(|(_, placement)| placement.coords.clone())
+                .collect();
+            covered.sort();
+            let mut expected = region.clone();
+            expected.sort();
+            assert_eq!(covered, expected);
+
+            assert_eq!(solution.iter().filter(|(idx, _)| *idx == 0).count(), 1);
+            assert_eq!(solution.iter().filter(|(idx, _)| *idx == 1).count(), 3);
+
+            // Placements match the pieces they were made from.
+            for (idx, placement) in solution {
+                let coords: Vec<Axial> = pieces[*idx]
+                    .0
+                    .iter()
+                    .map(|(coord, _)| coord.apply_transform(placement.transform))
+                    .collect();
+                assert_eq!(coords, placement.coords);
+            }
+        }
+
+        // Limits stop the search early.
+        assert_eq!(
+            super::solve_tiling(region.clone(), &pieces, false, Some(1)).len(),
+            1
+        );
+
+        // The area of the pieces has to match the region.
+        assert!(super::solve_tiling(region.clone(), &[(pair.clone(), 3)], false, None).is_empty());
+        assert!(super::solve_tiling(region, &[(pair, 4)], false, None).is_empty());
+    }
+
+    #[test]
+    fn solve_tiling_reflections() {
+        // A piece with no symmetry only covers its mirror image when it can be reflected.
+        let mut piece = HexShape::make_line(2, 0, true, |_| ());
+        piece.get_hexes_mut()[[0, 1]] = Some(());
+        let mut mirrored = piece.clone();
+        mirrored.mirror(Axes::Q);
+        let region: Vec<Axial> = mirrored.iter().map(|(coord, _)| coord).collect();
+
+        assert!(super::solve_tiling(region.clone(), &[(piece.clone(), 1)], false, None).is_empty());
+        assert_eq!(
+            super::solve_tiling(region, &[(piece, 1)], true, None).len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Shapes that carry edge and vertex data alongside their tiles.
+
+use crate::lib::*;
+
+use crate::core::{collection::Collection, transform::Transform};
+
+use super::{
+    coordinate::{Axes, Axial},
+    edge::Edge,
+    shape::HexShape,
+    vertex::Vertex,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A shape that also stores data on edges and vertices.
+///
+/// Tiles are stored in a [`HexShape`] and edges and vertices are keyed in the same local space as
+/// the shape's tiles. The shape's transform is used for all three so walls, doors and pre-placed
+/// developments stay attached to their tiles when the prefab is moved or rotated.
+///
+/// The shape is only changed through the prefab, a shape method that moves the tiles in local space, like
+/// [`HexShape::bake`], would leave the edges and vertices behind. Move the prefab with its own transform
+/// methods and change tile data with [`Self::iter_tiles_mut`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct HexPrefab<T: Clone, E: Clone, V: Clone> {
+    /// The tiles of the prefab, its transform is shared by the edges and vertices.
+    shape: HexShape<T>,
+    /// Edge data in shape local space.
+    edges: BTreeMap<Edge, E>,
+    /// Vertex data in shape local space.
+    vertices: BTreeMap<Vertex, V>,
+}
+
+impl<T: Clone, E: Clone, V: Clone> HexPrefab<T, E, V> {
+    /// Create a new prefab from a shape.
+    ///
+    /// The prefab will start with no edge or vertex data.
+    ///
+    /// ```
+    /// use gridava::hex::prefab::HexPrefab;
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let my_prefab: HexPrefab<i32, bool, u8> =
+    ///     HexPrefab::new(HexShape::make_hexagon(1, 0, false, |_| 1));
+    /// ```
+    pub fn new(shape: HexShape<T>) -> Self {
+        Self {
+            shape,
+            edges: BTreeMap::new(),
+            vertices: BTreeMap::new(),
+        }
+    }
+
+    /// Set the data of an edge.
+    ///
+    /// `edge` is in shape local space.
+    ///
+    /// ```
+    /// use gridava::hex::edge::{Edge, EdgeDirection, edge};
+    /// use gridava::hex::prefab::HexPrefab;
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_prefab: HexPrefab<i32, bool, u8> =
+    ///     HexPrefab::new(HexShape::make_hexagon(1, 0, false, |_| 1));
+    /// /// Put a wall on the west side of the tile (1, 1)
+    /// my_prefab.set_edge(edge!(1, 1, EdgeDirection::West), true);
+    /// ```
+    pub fn set_edge(&mut self, edge: Edge, data: E) -> &Self {
+        self.edges.insert(edge, data);
+        self
+    }
+
+    /// Set the data of a vertex.
+    ///
+    /// `vertex` is in shape local space.
+    ///
+    /// ```
+    /// use gridava::hex::vertex::{Vertex, VertexSpin, vertex};
+    /// use gridava::hex::prefab::HexPrefab;
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_prefab: HexPrefab<i32, bool, u8> =
+    ///     HexPrefab::new(HexShape::make_hexagon(1, 0, false, |_| 1));
+    /// /// Put a development on top of the tile (1, 1)
+    /// my_prefab.set_vertex(vertex!(1, 1, VertexSpin::Up), 1);
+    /// ```
+    pub fn set_vertex(&mut self, vertex: Vertex, data: V) -> &Self {
+        self.vertices.insert(vertex, data);
+        self
+    }
+
+    /// Get a reference to the prefab's edge data, keyed in shape local space.
+    pub fn get_edges(&self) -> &BTreeMap<Edge, E> {
+        &self.edges
+    }
+
+    /// Get a mutable reference to the prefab's edge data, keyed in shape local space.
+    pub fn get_edges_mut(&mut self) -> &mut BTreeMap<Edge, E> {
+        &mut self.edges
+    }
+
+    /// Get a reference to the prefab's vertex data, keyed in shape local space.
+    pub fn get_vertices(&self) -> &BTreeMap<Vertex, V> {
+        &self.vertices
+    }
+
+    /// Get a mutable reference to the prefab's vertex data, keyed in shape local space.
+    pub fn get_vertices_mut(&mut self) -> &mut BTreeMap<Vertex, V> {
+        &mut self.vertices
+    }
+
+    /// Get a reference to the prefab's shape.
+    pub fn get_shape(&self) -> &HexShape<T> {
+        &self.shape
+    }
+
+    /// Iterate mutably over the prefab's tiles in the parent space.
+    ///
+    /// Only the data can be changed, the tiles stay where they are. See [`HexShape::iter_mut`].
+    ///
+    /// ```
+    /// use gridava::hex::prefab::HexPrefab;
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_prefab: HexPrefab<i32, bool, u8> =
+    ///     HexPrefab::new(HexShape::make_hexagon(1, 0, false, |_| 1));
+    /// for (coord, tile) in my_prefab.iter_tiles_mut() {
+    ///     *tile = coord.q;
+    /// }
+    /// ```
+    pub fn iter_tiles_mut(&mut self) -> impl Iterator<Item = (Axial, &mut T)> {
+        self.shape.iter_mut()
+    }
+
+    /// Get the transform shared by the tiles, edges and vertices.
+    pub fn transform(&self) -> Transform<Axial> {
+        self.shape.transform
+    }
+
+    /// Set a new origin for the prefab.
+    ///
+    /// See [`HexShape::set_origin`].
+    pub fn set_origin(&mut self, new_origin: Transform<Axial>) -> &Self {
+        self.shape.set_origin(new_origin);
+        self
+    }
+
+    /// Translate the prefab.
+    ///
+    /// See [`HexShape::translate`].
+    pub fn translate(&mut self, coord: Axial) -> &Self {
+        self.shape.translate(coord);
+        self
+    }
+
+    /// Rotates the prefab, either about its local origin or some point.
+    ///
+    /// See [`HexShape::rotate`].
+    pub fn rotate(&mut self, coord: Option<Axial>, rot_dir: i32) -> &Self {
+        self.shape.rotate(coord, rot_dir);
+        self
+    }
+
+    /// Mirrors the prefab across an axis through its origin.
+    ///
+    /// See [`HexShape::mirror`].
+    pub fn mirror(&mut self, axes: Axes) -> &Self {
+        self.shape.mirror(axes);
+        self
+    }
+
+    /// Apply a prefab to a collection.
+    ///
+    /// Transforms the tiles, edges and vertices according to the shape's transform before setting
+    /// the data into the collection.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::Collection;
+    /// use gridava::hex::{coordinate::Axial, edge::Edge, vertex::Vertex};
+    /// use gridava::hex::prefab::HexPrefab;
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// #[derive(Default)]
+    /// struct Board {
+    ///     tiles: HashMap<Axial, i32>,
+    ///     walls: HashMap<Edge, bool>,
+    ///     towers: HashMap<Vertex, u8>,
+    /// }
+    ///
+    /// impl Collection<Axial, i32> for Board {
+    ///     fn set(&mut self, coord: Axial, data: i32) {
+    ///         self.tiles.insert(coord, data);
+    ///     }
+    /// }
+    ///
+    /// impl Collection<Edge, bool> for Board {
+    ///     fn set(&mut self, coord: Edge, data: bool) {
+    ///         self.walls.insert(coord, data);
+    ///     }
+    /// }
+    ///
+    /// impl Collection<Vertex, u8> for Board {
+    ///     fn set(&mut self, coord: Vertex, data: u8) {
+    ///         self.towers.insert(coord, data);
+    ///     }
+    /// }
+    ///
+    /// let mut board = Board::default();
+    /// HexPrefab::new(HexShape::make_hexagon(1, 0, false, |_| 1)).apply_shape(&mut board);
+    /// ```
+    pub fn apply_shape<COL>(&self, col: &mut COL)
+    where
+        COL: Collection<Axial, T> + Collection<Edge, E> + Collection<Vertex, V>,
+    {
+        let transform = self.transform();
+
+        self.shape.apply_shape(col);
+
+        self.edges.iter().for_each(|(edge, data)| {
+            col.set(edge.apply_transform(transform), data.clone());
+        });
+
+        self.vertices.iter().for_each(|(vertex, data)| {
+            col.set(vertex.apply_transform(transform), data.clone());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        axial,
+        hex::{
+            edge::{edge, EdgeDirection},
+            vertex::{vertex, VertexSpin},
+        },
+    };
+
+    #[derive(Default)]
+    struct MockCollection {
+        tiles: HashMap<Axial, i32>,
+        edges: HashMap<Edge, char>,
+        vertices: HashMap<Vertex, u8>,
+    }
+
+    impl Collection<Axial, i32> for MockCollection {
+        fn set(&mut self, coord: Axial, data: i32) {
+            self.tiles.insert(coord, data);
+        }
+    }
+
+    impl Collection<Edge, char> for MockCollection {
+        fn set(&mut self, coord: Edge, data: char) {
+            self.edges.insert(coord, data);
+        }
+    }
+
+    impl Collection<Vertex, u8> for MockCollection {
+        fn set(&mut self, coord: Vertex, data: u8) {
+            self.vertices.insert(coord, data);
+        }
+    }
+
+    fn make_prefab() -> HexPrefab<i32, char, u8> {
+        let mut prefab = HexPrefab::new(HexShape::make_line(1, 0, false, |_| 1));
+        prefab.set_edge(edge!(1, 0, EdgeDirection::West), 'd');
+        prefab.set_vertex(vertex!(0, 0, VertexSpin::Up), 2);
+        prefab
+    }
+
+    #[test]
+    fn new() {
+        let prefab = HexPrefab::<i32, char, u8>::new(HexShape::make_line(1, 0, false, |_| 1));
+        assert_eq!(*prefab.get_shape(), HexShape::make_line(1, 0, false, |_| 1));
+        assert!(prefab.get_edges().is_empty());
+        assert!(prefab.get_vertices().is_empty());
+    }
+
+    #[test]
+    fn set_edge() {
+        let mut prefab = make_prefab();
+        prefab.set_edge(edge!(1, 0, EdgeDirection::West), 'w');
+        assert_eq!(prefab.get_edges().len(), 1);
+        assert_eq!(
+            prefab.get_edges().get(&edge!(1, 0, EdgeDirection::West)),
+            Some(&'w')
+        );
+
+        prefab.get_edges_mut().clear();
+        assert!(prefab.get_edges().is_empty());
+    }
+
+    #[test]
+    fn set_vertex() {
+        let mut prefab = make_prefab();
+        prefab.set_vertex(vertex!(1, 0, VertexSpin::Down), 3);
+        assert_eq!(prefab.get_vertices().len(), 2);
+        assert_eq!(
+            prefab.get_vertices().get(&vertex!(1, 0, VertexSpin::Down)),
+            Some(&3)
+        );
+
+        prefab.get_vertices_mut().clear();
+        assert!(prefab.get_vertices().is_empty());
+    }
+
+    #[test]
+    fn translate_rotate() {
+        let mut prefab = make_prefab();
+        prefab.translate(axial!(2, 1));
+        prefab.rotate(None, 1);
+        assert_eq!(prefab.transform(), prefab.get_shape().transform);
+        assert_eq!(prefab.transform().translation, axial!(2, 1));
+        assert_eq!(prefab.transform().rotation, 1);
+
+        prefab.set_origin(Transform::default());
+        assert_eq!(prefab.transform(), Transform::default());
+    }
+
+    #[test]
+    fn iter_tiles_mut() {
+        let mut prefab = make_prefab();
+        prefab.translate(axial!(2, 1));
+        for (coord, tile) in prefab.iter_tiles_mut() {
+            *tile = coord.q;
+        }
+
+        // The data changes but the tiles stay under their edges and vertices.
+        assert_eq!(prefab.get_shape().get(axial!(3, 1)), Some(&3));
+        assert_eq!(prefab.transform().translation, axial!(2, 1));
+        assert_eq!(prefab.get_edges().len(), 1);
+    }
+
+    #[test]
+    fn apply_shape() {
+        let mut prefab = make_prefab();
+        prefab.rotate(None, 1);
+        prefab.translate(axial!(2, 1));
+
+        let mut col = MockCollection::default();
+        prefab.apply_shape(&mut col);
+
+        // Tiles (0, 0) and (1, 0) rotate to (0, 0) and (0, 1) then move by (2, 1).
+        assert_eq!(col.tiles.len(), 2);
+        assert_eq!(col.tiles.get(&axial!(2, 1)), Some(&1));
+        assert_eq!(col.tiles.get(&axial!(2, 2)), Some(&1));
+
+        // The door between the tiles stays between the tiles.
+        assert_eq!(col.edges.len(), 1);
+        let (door, _) = col.edges.iter().next().unwrap();
+        let mut sides = door.adjacent_hexes();
+        sides.sort();
+        assert_eq!(sides, [axial!(2, 1), axial!(2, 2)]);
+
+        // The vertex on top of (0, 0) is now the top right corner of (2, 1).
+        assert_eq!(col.vertices.get(&vertex!(3, 0, VertexSpin::Down)), Some(&2));
+    }
+
+    #[test]
+    fn mirror() {
+        let mut prefab = make_prefab();
+        prefab.translate(axial!(2, 1));
+        prefab.mirror(Axes::S);
+
+        let mut col = MockCollection::default();
+        prefab.apply_shape(&mut col);
+
+        // Tiles (0, 0) and (1, 0) mirror to (0, 0) and (0, 1) then move by (2, 1).
+        assert_eq!(col.tiles.len(), 2);
+        assert_eq!(col.tiles.get(&axial!(2, 1)), Some(&1));
+        assert_eq!(col.tiles.get(&axial!(2, 2)), Some(&1));
+
+        // The door between the tiles stays between the tiles.
+        let (door, _) = col.edges.iter().next().unwrap();
+        let mut sides = door.adjacent_hexes();
+        sides.sort();
+        assert_eq!(sides, [axial!(2, 1), axial!(2, 2)]);
+
+        // The vertex on top of (0, 0) is now the bottom left corner of (2, 1).
+        assert_eq!(col.vertices.get(&vertex!(1, 2, VertexSpin::Up)), Some(&2));
+    }
+}
@@ -5,13 +5,18 @@ use crate::lib::*;
 use crate::{
     axial,
     core::{
-        collection::Collection,
+        collection::{Collection, ReadCollection},
         transform::{Transform, Vector2D},
     },
     transform, vector2d,
 };
 
-use super::coordinate::Axial;
+use super::{
+    algorithms::{outline, Outline},
+    bounds::HexBounds,
+    coordinate::{Axes, Axial, HexDirection},
+    storage::ShapeStorage,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -21,13 +26,104 @@ use serde::{Deserialize, Serialize};
 /// Each coordinate is a vector that 'points' to the origin coordinate creating a shape local space.
 /// The transformation matrix associated to the shape is then used to convert this local space to the
 /// coordinate space of the parent.
+///
+/// Tiles are kept in a [`ShapeStorage`], a dense array by default, see [`super::storage`] for the others.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Debug, Default)]
-pub struct HexShape<T: Clone> {
-    /// 2D array of tiles, index corresponds to coordinate (q, r). Origin of (0,0) to (+∞,+∞).
-    shape: Array2<Option<T>>,
+pub struct HexShape<T: Clone, S = Array2<Option<T>>> {
+    /// Tiles in shape local space, see [`ShapeStorage`]. Origin of (0,0) to (+∞,+∞).
+    shape: S,
     /// The transformation matrix to convert from parent grid to local space.
     pub transform: Transform<Axial>,
+    /// The type of the tiles in the storage.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tile: PhantomData<T>,
+}
+
+/// How [`HexShape::scale`] chooses the tiles of a scaled shape.
+///
+/// A scaled tile's position in the source usually falls between source tiles, inside the triangle formed by
+/// the three nearest tile centers. Both methods take the data of the nearest occupied source tile.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ScaleSampling {
+    /// Keep a tile only if every source tile it falls between is occupied.
+    ///
+    /// Shapes made by [`HexShape::make_shape`] stay the same shape at integer factors, a hexagon of size 2
+    /// scaled by 3 is a hexagon of size 6.
+    #[default]
+    Nearest,
+    /// Keep a tile if the source tiles it falls between are mostly occupied, weighted by their distance.
+    ///
+    /// Fills in thin gaps and corners, at the cost of growing shapes at larger factors.
+    Majority,
+}
+
+/// A closure merging existing data with incoming data, called as `combine(existing, incoming)`.
+pub type MergeFn<'a, T> = Box<dyn FnMut(&T, &T) -> T + 'a>;
+
+/// How [`HexShape::apply_shape_with`] treats tiles the collection already has data for.
+pub enum MergePolicy<'a, T> {
+    /// Replace the existing data.
+    Overwrite,
+    /// Leave the existing data and skip the tile.
+    KeepExisting,
+    /// Replace the existing data with the result of the closure.
+    Combine(MergeFn<'a, T>),
+    /// Write nothing at all if any tile already has data.
+    FailOnConflict,
+}
+
+/// The outcome of applying a shape to a collection.
+///
+/// Every tile of the shape is either written or skipped, conflicts are the tiles that already had data
+/// whatever the policy did with them. Coordinates are in the order of [`HexShape::iter`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct MergeReport {
+    /// Coordinates that were set in the collection.
+    pub written: Vec<Axial>,
+    /// Coordinates that were left as they were.
+    pub skipped: Vec<Axial>,
+    /// Coordinates the collection already had data for.
+    pub conflicts: Vec<Axial>,
+}
+
+impl MergeReport {
+    /// Check if the shape was applied without meeting any existing data.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+// Weights this small are treated as a position lying on an edge or center of the sampling triangle.
+const SAMPLE_EPSILON: f64 = 1e-9;
+
+// Round a float down to an integer, without depending on std.
+fn floor(value: f64) -> i32 {
+    let truncated = value as i32;
+    match (truncated as f64) > value {
+        true => truncated - 1,
+        false => truncated,
+    }
+}
+
+// The tile centers around a fractional coordinate along with their barycentric weights.
+fn sample_corners(q: f64, r: f64) -> [(Axial, f64); 3] {
+    let (base_q, base_r) = (floor(q), floor(r));
+    let (u, v) = (q - base_q as f64, r - base_r as f64);
+
+    match u + v <= 1.0 {
+        true => [
+            (axial!(base_q, base_r), 1.0 - u - v),
+            (axial!(base_q + 1, base_r), u),
+            (axial!(base_q, base_r + 1), v),
+        ],
+        false => [
+            (axial!(base_q + 1, base_r), 1.0 - v),
+            (axial!(base_q, base_r + 1), 1.0 - u),
+            (axial!(base_q + 1, base_r + 1), u + v - 1.0),
+        ],
+    }
 }
 
 impl<T: Clone> HexShape<T> {
@@ -45,9 +141,10 @@ impl<T: Clone> HexShape<T> {
         Self {
             shape: match shape {
                 Some(arr) => arr,
-                None => array![[], []],
+                None => Array::from_shape_simple_fn((0, 0), || None),
             },
             transform: transform.unwrap_or_default(),
+            tile: PhantomData,
         }
     }
 
@@ -68,7 +165,7 @@ impl<T: Clone> HexShape<T> {
     ///
     /// The algorithm *WILL* calculate its inequalities on EVERY point in the array. So, in example, if you have a point
     /// inside a shape, that point will still be calculated but will not change anything about the resultant inequality.
-    pub fn make_shape<F>(points: &[Axial], square_bb: bool, mut constructor: F) -> Self
+    pub fn make_shape<F>(points: &[Axial], square_bb: bool, constructor: F) -> Self
     where
         F: FnMut(Axial) -> T,
     {
@@ -77,58 +174,54 @@ impl<T: Clone> HexShape<T> {
             return HexShape::new(None, None);
         }
 
-        // Assign our transform.
-        let transform = transform!(axial!(0, 0));
-
-        // Compute a system of linear inequalities.
-        let mut q_min = i32::MAX;
-        let mut q_max = i32::MIN;
-        let mut r_min = i32::MAX;
-        let mut r_max = i32::MIN;
-        let mut s_min = i32::MAX;
-        let mut s_max = i32::MIN;
-        for point in points {
-            q_min = q_min.min(point.q);
-            q_max = q_max.max(point.q);
-            r_min = r_min.min(point.r);
-            r_max = r_max.max(point.r);
-            let s = point.compute_s();
-            s_min = s_min.min(s);
-            s_max = s_max.max(s);
-        }
-
         // Solve for all the hexes inside an inequality.
-        let hexes: Vec<Axial> = (q_min..=q_max)
-            .flat_map(|q| {
-                (r_min.max(-q - s_max)..=r_max.min(-q - s_min))
-                    .map(move |r| axial!(q - q_min, r - r_min))
-            })
-            .collect();
+        let hexes = HexBounds::from_points(points.iter().copied())
+            .unwrap()
+            .hexes();
+
+        Self::make_local(hexes, square_bb, constructor)
+    }
+
+    // Build a shape from coordinates moved so their minimum q and r is (0, 0).
+    //
+    // The array is tightly bound or square according to `square_bb`, the constructor is given the moved
+    // coordinates in order of q then r.
+    pub(crate) fn make_local<I, F>(coords: I, square_bb: bool, mut constructor: F) -> Self
+    where
+        I: IntoIterator<Item = Axial>,
+        F: FnMut(Axial) -> T,
+    {
+        let coords: BTreeSet<Axial> = coords.into_iter().collect();
+        let Some(first) = coords.first() else {
+            return HexShape::new(None, None);
+        };
+
+        let (mut min, mut max) = (*first, *first);
+        for coord in &coords {
+            min = axial!(min.q.min(coord.q), min.r.min(coord.r));
+            max = axial!(max.q.max(coord.q), max.r.max(coord.r));
+        }
 
-        // Find the bounding size of this inequality as a square or tightly
+        // Find the bounding size of the coordinates as a square or tightly
         // bound according to the value of square_bb.
         let size = match square_bb {
             true => {
-                let largest = (q_max - q_min)
-                    .unsigned_abs()
-                    .max((r_max - r_min).unsigned_abs());
-                ((largest + 1) as usize, (largest + 1) as usize)
+                let largest = (max.q - min.q).max(max.r - min.r) as usize;
+                (largest + 1, largest + 1)
             }
-            false => (
-                ((q_max - q_min).unsigned_abs() + 1) as usize,
-                ((r_max - r_min).unsigned_abs() + 1) as usize,
-            ),
+            false => ((max.q - min.q + 1) as usize, (max.r - min.r + 1) as usize),
         };
 
         // Create our array.
         let mut arr = Array::from_shape_simple_fn(size, || None);
 
         // Construct tiles that the shape contains.
-        for coord in hexes {
-            arr[[coord.q as usize, coord.r as usize]] = Some(constructor(coord));
+        for coord in coords {
+            let local = coord - min;
+            arr[[local.q as usize, local.r as usize]] = Some(constructor(local));
         }
 
-        HexShape::new(Some(arr), Some(transform))
+        HexShape::new(Some(arr), Some(transform!(axial!(0, 0))))
     }
 
     /// Create a line shape.
@@ -241,116 +334,193 @@ impl<T: Clone> HexShape<T> {
         )
     }
 
-    /// Translate the shape.
+    /// Create a ring shape.
     ///
-    /// Mutates the transform of the shape.
+    /// Given a radius and thickness this will create the tiles whose distance from the center is within
+    /// `thickness` of `radius`. A thickness of 1 is a ring one tile wide, a thickness greater than the radius
+    /// is a filled hexagon and a thickness of 0 is empty.
+    ///
+    /// see [`Self::make_shape`] for more.
     ///
     /// ```
-    /// use gridava::hex::coordinate::{Axial, axial};
     /// use gridava::hex::shape::HexShape;
     ///
-    /// let mut my_shape: HexShape<i32> = HexShape::new(None, None);
-    /// /// Move the shape in the positive q and r axes by 1.
-    /// my_shape.translate(axial!(1, 1));
+    /// /// Creates the 12 tiles at distance 2 from the center.
+    /// let my_shape = HexShape::make_ring(2, 1, true, |_| 1);
     /// ```
-    pub fn translate(&mut self, coord: Axial) -> &Self {
-        self.transform.translation += coord;
-        self
+    pub fn make_ring<F>(radius: u32, thickness: u32, square_bb: bool, constructor: F) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        let inner = radius as i32 - thickness as i32;
+        let coords = axial!(0, 0)
+            .range(radius as i32)
+            .into_iter()
+            .filter(|coord| coord.distance(axial!(0, 0)) > inner);
+
+        Self::make_local(coords, square_bb, constructor)
     }
 
-    /// Rotate the shape.
+    /// Create a cone shape.
     ///
-    /// Mutates the transform of the shape.
+    /// Given a length, angle and direction this will create a wedge spreading out from a point. The angle is
+    /// the number of 60 degree sextants the cone covers, clockwise starting from the direction. An angle of 1
+    /// is a triangle, 3 is half a hexagon and 6 or more is a hexagon.
     ///
-    /// `rot_dir`: positive denotes CW, negative CCW, magnitude denotes how many 60 degree rotations.
+    /// see [`Self::make_shape`] for more.
     ///
     /// ```
-    /// use gridava::hex::coordinate::{Axial, axial};
     /// use gridava::hex::shape::HexShape;
     ///
-    /// /// The shape has an origin of (0, 0)
-    /// let mut my_shape: HexShape<i32> = HexShape::new(None, None);
-    /// /// Rotate the shape around the coordinate (1, 1) clockwise.
-    /// my_shape.rotate_about(axial!(1, 1), 1);
+    /// /// Creates a 120 degree cone 3 tiles long.
+    /// let my_shape = HexShape::make_cone(3, 2, 0, true, |_| 1);
     /// ```
-    pub fn rotate_about(&mut self, coord: Axial, rot_dir: i32) -> &Self {
-        self.transform.translation = self.transform.translation.rotate(Some(coord), rot_dir);
-        self.transform.rotation += rot_dir;
-        self
+    pub fn make_cone<F>(
+        length: u32,
+        angle: u32,
+        rot_dir: i32,
+        square_bb: bool,
+        constructor: F,
+    ) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        let length = length as i32;
+        let mut coords = vec![];
+        for sextant in 0..angle.min(6) as i32 {
+            for a in 0..=length {
+                for b in 0..=length - a {
+                    let coord = axial!(0, 0)
+                        .make_vector(a, rot_dir + sextant)
+                        .make_vector(b, rot_dir + sextant + 1);
+                    coords.push(coord);
+                }
+            }
+        }
+
+        Self::make_local(coords, square_bb, constructor)
     }
 
-    /// Rotates the shape, either about its local origin or some point.
+    /// Create a parallelogram shape.
     ///
-    /// Mutates the transform of the shape.
+    /// Given two side lengths and a direction this will create a parallelogram, the first side runs along
+    /// the direction and the second one step clockwise from it. Equal sides make a rhombus.
     ///
-    /// `coord`: Some denotes about a specific point, None denotes around the shape's center.
+    /// see [`Self::make_shape`] for more.
     ///
-    /// `rot_dir`: positive denotes CW, negative CCW, magnitude denotes how many 60 degree rotations.
     /// ```
     /// use gridava::hex::shape::HexShape;
-    /// use gridava::hex::coordinate::{Axial, axial};
     ///
-    /// /// Rotate the shape about the coordinate (1, 1) clockwise.
-    /// HexShape::<i32>::new(None, None).rotate(Some(axial!(1, 1)), 1);
+    /// /// Creates a parallelogram of 4 by 2 tiles, 0-3 and 0-1 inclusive.
+    /// let my_shape = HexShape::make_parallelogram(3, 1, 0, true, |_| 1);
     /// ```
-    pub fn rotate(&mut self, coord: Option<Axial>, rot_dir: i32) -> &Self {
-        match coord {
-            Some(coord) => self.rotate_about(coord, rot_dir),
-            None => {
-                self.transform.rotation += rot_dir;
-                self
-            }
-        }
+    pub fn make_parallelogram<F>(
+        width: u32,
+        height: u32,
+        rot_dir: i32,
+        square_bb: bool,
+        constructor: F,
+    ) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        // Working in local space
+        let vertex_a = axial!(0, 0);
+        let vertex_b = vertex_a.make_vector(width as i32, rot_dir);
+        let vertex_c = vertex_a.make_vector(height as i32, rot_dir + 1);
+        let vertex_d = vertex_b.make_vector(height as i32, rot_dir + 1);
+
+        Self::make_shape(
+            &[vertex_a, vertex_b, vertex_c, vertex_d],
+            square_bb,
+            constructor,
+        )
     }
 
-    /// Scale a shape.
+    /// Create a rectangle shape.
+    ///
+    /// Given a width and height in tiles this will create a rectangular map, rows of `width` tiles along the
+    /// q axis with every other row shifted by half a tile so the rows line up as a rectangle in world space
+    /// for pointy top hexes.
     ///
-    /// Mutates the internal array itself.
+    /// see [`Self::make_shape`] for more.
     ///
     /// ```
     /// use gridava::hex::shape::HexShape;
-    /// use gridava::core::transform::{Vector2D, vector2d};
     ///
-    /// let mut my_shape: HexShape<i32> = HexShape::new(None, None);
-    /// my_shape.scale(vector2d!(2.0, 2.0));
+    /// /// Creates a map 8 tiles wide and 4 tall.
+    /// let my_shape = HexShape::make_rectangle(8, 4, false, |_| 1);
     /// ```
-    pub fn scale(mut self, scale: Vector2D<f32>) -> Self {
-        // Uses bilinear interpolation algorithm, it's lossless  meaning if you apply a scale and then its inverse
-        //  it will return to its original shape.
-
-        let shape = self.shape.shape();
-
-        let new_x = (shape[0] as f32 * scale.x).round() as usize;
-        let new_y = (shape[1] as f32 * scale.y).round() as usize;
-
-        let mut new_arr = Array2::from_shape_simple_fn((new_x, new_y), || None);
+    pub fn make_rectangle<F>(width: u32, height: u32, square_bb: bool, constructor: F) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        let coords = (0..height as i32).flat_map(|r| {
+            let offset = -r.div_euclid(2);
+            (offset..offset + width as i32).map(move |q| axial!(q, r))
+        });
 
-        let x_ratio = (shape[0] as f32) / (new_x as f32);
-        let y_ratio = (shape[1] as f32) / (new_y as f32);
+        Self::make_local(coords, square_bb, constructor)
+    }
 
-        for y in 0..new_y {
-            for x in 0..new_x {
-                // Map new indices to old indices directly using ratios
-                //  Using .round() here to preserve inverse scaling returns to input
-                let src_x = (x as f32 * x_ratio).round() as usize;
-                let src_y = (y as f32 * y_ratio).round() as usize;
+    /// Create a star shape.
+    ///
+    /// Given a size this will create a hexagram, the union of two triangles pointing in opposite directions.
+    /// The center is a hexagon of the size, with a point on each of its sides reaching twice as far.
+    ///
+    /// see [`Self::make_shape`] for more.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// /// Creates a star with a hexagon of size 1 in its center and 6 points of 1 tile.
+    /// let my_shape = HexShape::make_star(1, true, |_| 1);
+    /// ```
+    pub fn make_star<F>(size: u32, square_bb: bool, constructor: F) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        let size = size as i32;
+        let coords = axial!(0, 0).range(2 * size).into_iter().filter(|coord| {
+            let (q, r, s) = (coord.q, coord.r, coord.compute_s());
+            (q >= -size && r >= -size && s >= -size) || (q <= size && r <= size && s <= size)
+        });
 
-                // Clamp indices to prevent out-of-bounds access
-                let src_x_clamped = src_x.min(shape[0] - 1);
-                let src_y_clamped = src_y.min(shape[1] - 1);
+        Self::make_local(coords, square_bb, constructor)
+    }
 
-                // Assign the corresponding tile from the source array
-                new_arr[[x, y]] = self.shape[[src_x_clamped, src_y_clamped]].clone();
-            }
+    /// Create an irregular hexagon shape.
+    ///
+    /// Given six side lengths and a direction this will walk the sides of a hexagon, turning clockwise after
+    /// each one starting from the direction. The sides only close into a hexagon when opposite sides differ by
+    /// the same amount, `sides[0] - sides[3] == sides[4] - sides[1] == sides[2] - sides[5]`, otherwise the
+    /// shape covers the corners that were walked.
+    ///
+    /// see [`Self::make_shape`] for more.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// /// Creates a hexagon stretched along one axis.
+    /// let my_shape = HexShape::make_irregular_hexagon([3, 1, 1, 3, 1, 1], 0, true, |_| 1);
+    /// ```
+    pub fn make_irregular_hexagon<F>(
+        sides: [u32; 6],
+        rot_dir: i32,
+        square_bb: bool,
+        constructor: F,
+    ) -> Self
+    where
+        F: FnMut(Axial) -> T,
+    {
+        // Working in local space
+        let mut vertices = vec![axial!(0, 0)];
+        for (side, length) in sides.iter().enumerate() {
+            let last = vertices[vertices.len() - 1];
+            vertices.push(last.make_vector(*length as i32, rot_dir + side as i32));
         }
-        self.shape = new_arr;
-        self
-    }
 
-    /// Set a new origin for the shape.
-    pub fn set_origin(&mut self, new_origin: Transform<Axial>) -> &Self {
-        self.transform = new_origin;
-        self
+        Self::make_shape(&vertices, square_bb, constructor)
     }
 
     /// Overwrite the internal working array of the shape.
@@ -359,23 +529,6 @@ impl<T: Clone> HexShape<T> {
         self
     }
 
-    /// Apply a shape to a collection
-    ///
-    /// Transforms the coordinates according to the transform. before setting the data into the collection
-    ///
-    /// See the colony game example for a usecase.
-    pub fn apply_shape<COL: Collection<Axial, T>>(&self, col: &mut COL) {
-        self.shape.indexed_iter().for_each(|ele| {
-            if let Some(value) = ele.1.as_ref() {
-                // Apply transform
-                let coord =
-                    axial!(ele.0 .0 as i32, ele.0 .1 as i32).apply_transform(self.transform);
-
-                col.set(coord, value.clone());
-            }
-        });
-    }
-
     /// Get a reference to the shape's tile array.
     ///
     /// # Example
@@ -413,68 +566,928 @@ impl<T: Clone> HexShape<T> {
     pub fn get_hexes_mut(&mut self) -> &mut Array2<Option<T>> {
         &mut self.shape
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
+    /// Copy a region of a collection into a new shape.
+    ///
+    /// Coordinates the collection has no data for are left empty. The shape is tightly bound around the
+    /// copied tiles, see [`HexShape::from_iter`]. The data is copied as it is, the shape starts with an
+    /// identity transform so it faces the same way as in the collection.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::{Collection, ReadCollection};
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// #[derive(Default)]
+    /// struct Level {
+    ///     tiles: HashMap<Axial, i32>,
+    /// }
+    ///
+    /// impl Collection<Axial, i32> for Level {
+    ///     fn set(&mut self, coord: Axial, data: i32) {
+    ///         self.tiles.insert(coord, data);
+    ///     }
+    /// }
+    ///
+    /// impl ReadCollection<Axial, i32> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&i32> {
+    ///         self.tiles.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let mut level = Level::default();
+    /// HexShape::make_hexagon(3, 0, false, |_| 1).apply_shape(&mut level);
+    ///
+    /// // Copy everything within 1 of the tile (3, 3).
+    /// let copied = HexShape::extract(&level, axial!(3, 3).range(1));
+    ///
+    /// // Copy the tiles under another shape.
+    /// let mask = HexShape::make_triangle(2, 0, false, |_| ());
+    /// let copied = HexShape::extract(&level, mask.iter().map(|(coord, _)| coord));
+    ///
+    /// // Paste it somewhere else.
+    /// let mut pasted = copied.clone();
+    /// pasted.translate(axial!(10, 0));
+    /// pasted.apply_shape(&mut level);
+    /// ```
+    pub fn extract<COL, I>(col: &COL, coords: I) -> Self
+    where
+        COL: ReadCollection<Axial, T>,
+        I: IntoIterator<Item = Axial>,
+    {
+        Self::extract_where(col, coords, |_, _| true)
+    }
 
-    use super::*;
-    use crate::{axial, core::collection::Collection};
+    /// Copy the tiles of a region of a collection that match a predicate into a new shape.
+    ///
+    /// See [`Self::extract`].
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::ReadCollection;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// struct Level {
+    ///     walls: HashMap<Axial, bool>,
+    /// }
+    ///
+    /// impl ReadCollection<Axial, bool> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&bool> {
+    ///         self.walls.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let level = Level { walls: HashMap::from([(axial!(0, 0), true), (axial!(1, 0), false)]) };
+    ///
+    /// // Capture the walls of a hand built room.
+    /// let walls = HexShape::extract_where(&level, axial!(0, 0).range(5), |_, wall| *wall);
+    /// ```
+    pub fn extract_where<COL, I, F>(col: &COL, coords: I, mut predicate: F) -> Self
+    where
+        COL: ReadCollection<Axial, T>,
+        I: IntoIterator<Item = Axial>,
+        F: FnMut(&Axial, &T) -> bool,
+    {
+        let tiles: BTreeMap<Axial, T> = coords
+            .into_iter()
+            .filter_map(|coord| {
+                col.get(&coord)
+                    .filter(|tile| predicate(&coord, tile))
+                    .map(|tile| (coord, tile.clone()))
+            })
+            .collect();
 
-    struct MockCollection {
-        tiles: HashMap<Axial, i32>,
+        Self::from_iter(tiles)
     }
+}
 
-    impl Collection<Axial, i32> for MockCollection {
-        fn set(&mut self, coord: Axial, data: i32) {
-            self.tiles.insert(coord, data);
+impl<T: Clone, S: ShapeStorage<T>> HexShape<T, S> {
+    /// Create a shape from tiles in the parent space, with any storage.
+    ///
+    /// The shape is tightly bound around the tiles with its transform translated to their minimum (q, r).
+    /// See [`HexShape::from_iter`] for the default dense storage.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    /// use gridava::hex::storage::SparseStorage;
+    ///
+    /// let my_shape: HexShape<i32, SparseStorage<i32>> =
+    ///     HexShape::from_tiles([(axial!(-1, 3), 1), (axial!(1, 2), 2)]);
+    /// ```
+    pub fn from_tiles<I>(tiles: I) -> Self
+    where
+        I: IntoIterator<Item = (Axial, T)>,
+    {
+        let tiles: Vec<(Axial, T)> = tiles.into_iter().collect();
+        if tiles.is_empty() {
+            return Self {
+                shape: S::from_tiles((0, 0), []),
+                transform: Transform::default(),
+                tile: PhantomData,
+            };
         }
-    }
-
-    #[test]
-    fn translate() {
-        let mut shape = HexShape::<i32>::new(None, None);
 
-        shape.translate(axial!(4, 6));
-        assert_eq!(shape.transform.translation, axial!(4, 6));
+        let (mut min, mut max) = (tiles[0].0, tiles[0].0);
+        for (coord, _) in &tiles {
+            min = axial!(min.q.min(coord.q), min.r.min(coord.r));
+            max = axial!(max.q.max(coord.q), max.r.max(coord.r));
+        }
 
-        shape.translate(axial!(2, -3));
-        assert_eq!(shape.transform.translation, axial!(6, 3));
+        let dim = ((max.q - min.q + 1) as usize, (max.r - min.r + 1) as usize);
+        Self {
+            shape: S::from_tiles(
+                dim,
+                tiles.into_iter().map(|(coord, tile)| (coord - min, tile)),
+            ),
+            transform: transform!(min),
+            tile: PhantomData,
+        }
     }
 
-    #[test]
-    fn rotate_about() {
-        let mut shape = HexShape::<i32>::new(None, None);
-
-        shape.rotate_about(axial!(1, 2), 0);
-        assert_eq!(shape.transform.translation, axial!(0, 0));
-        assert_eq!(shape.transform.rotation, 0);
-
-        shape.rotate_about(axial!(1, 2), 1);
-        assert_eq!(shape.transform.translation, axial!(3, -1));
-        assert_eq!(shape.transform.rotation, 1);
-
-        shape.rotate_about(axial!(1, 2), -1);
-        assert_eq!(shape.transform.translation, axial!(0, 0));
-        assert_eq!(shape.transform.rotation, 0);
-
-        shape.rotate_about(axial!(1, 2), 2);
-        assert_eq!(shape.transform.translation, axial!(4, 1));
-        assert_eq!(shape.transform.rotation, 2);
-
-        shape.rotate_about(axial!(1, 2), -2);
-        assert_eq!(shape.transform.translation, axial!(0, 0));
-        assert_eq!(shape.transform.rotation, 0);
+    /// Move the shape's tiles into another storage.
+    ///
+    /// The transform and local coordinates of the tiles are kept, see [`super::storage`].
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    /// use gridava::hex::storage::RunLengthStorage;
+    ///
+    /// // A long line only needs a single run instead of a square array.
+    /// let line = HexShape::make_line(100, 0, true, |_| 1);
+    /// let compact = line.clone().into_storage::<RunLengthStorage<i32>>();
+    ///
+    /// // And back again.
+    /// assert_eq!(compact.into_storage(), line);
+    /// ```
+    pub fn into_storage<S2: ShapeStorage<T>>(self) -> HexShape<T, S2> {
+        HexShape {
+            shape: S2::from_tiles(
+                self.shape.dim(),
+                self.shape.iter().map(|(coord, tile)| (coord, tile.clone())),
+            ),
+            transform: self.transform,
+            tile: PhantomData,
+        }
+    }
 
-        shape.rotate_about(axial!(1, 2), 3);
-        assert_eq!(shape.transform.translation, axial!(2, 4));
-        assert_eq!(shape.transform.rotation, 3);
+    /// Get a reference to the shape's storage.
+    pub fn storage(&self) -> &S {
+        &self.shape
     }
 
-    #[test]
-    fn rotate() {
-        let mut shape = HexShape::<i32>::new(None, None);
+    /// Translate the shape.
+    ///
+    /// Mutates the transform of the shape.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_shape: HexShape<i32> = HexShape::new(None, None);
+    /// /// Move the shape in the positive q and r axes by 1.
+    /// my_shape.translate(axial!(1, 1));
+    /// ```
+    pub fn translate(&mut self, coord: Axial) -> &Self {
+        self.transform.translation += coord;
+        self
+    }
+
+    /// Rotate the shape.
+    ///
+    /// Mutates the transform of the shape.
+    ///
+    /// `rot_dir`: positive denotes CW, negative CCW, magnitude denotes how many 60 degree rotations.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// /// The shape has an origin of (0, 0)
+    /// let mut my_shape: HexShape<i32> = HexShape::new(None, None);
+    /// /// Rotate the shape around the coordinate (1, 1) clockwise.
+    /// my_shape.rotate_about(axial!(1, 1), 1);
+    /// ```
+    pub fn rotate_about(&mut self, coord: Axial, rot_dir: i32) -> &Self {
+        self.transform.translation = self.transform.translation.rotate(Some(coord), rot_dir);
+        self.transform.rotation += rot_dir;
+        self
+    }
+
+    /// Rotates the shape, either about its local origin or some point.
+    ///
+    /// Mutates the transform of the shape.
+    ///
+    /// `coord`: Some denotes about a specific point, None denotes around the shape's center.
+    ///
+    /// `rot_dir`: positive denotes CW, negative CCW, magnitude denotes how many 60 degree rotations.
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    ///
+    /// /// Rotate the shape about the coordinate (1, 1) clockwise.
+    /// HexShape::<i32>::new(None, None).rotate(Some(axial!(1, 1)), 1);
+    /// ```
+    pub fn rotate(&mut self, coord: Option<Axial>, rot_dir: i32) -> &Self {
+        match coord {
+            Some(coord) => self.rotate_about(coord, rot_dir),
+            None => {
+                self.transform.rotation += rot_dir;
+                self
+            }
+        }
+    }
+
+    /// Mirrors the shape across an axis through its origin.
+    ///
+    /// Mutates the transform of the shape. Combined with [`Self::rotate`] a shape can be placed in all 12
+    /// orientations of a hexagon.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    /// use gridava::hex::coordinate::Axes;
+    ///
+    /// /// Mirror the shape across its q axis.
+    /// HexShape::<i32>::new(None, None).mirror(Axes::Q);
+    /// ```
+    pub fn mirror(&mut self, axes: Axes) -> &Self {
+        // Mirroring across any axis is the same as mirroring across the q axis then rotating.
+        let offset = match axes {
+            Axes::Q => 0,
+            Axes::R => 4,
+            Axes::S => 2,
+        };
+
+        self.transform.rotation = offset - self.transform.rotation;
+        self.transform.reflected = !self.transform.reflected;
+        self
+    }
+
+    /// Scale a shape in hex space.
+    ///
+    /// Tiles are scaled away from `pivot` by `factor`. `pivot` is a coordinate in the parent space, None will
+    /// scale about the shape's origin. Each tile of the result samples the source tiles around its position
+    /// scaled back by the inverse of `factor`, see [`ScaleSampling`] for how tiles are chosen.
+    ///
+    /// The shape is resampled in the parent space, so the result has no rotation and is tightly bound. The
+    /// factor is applied to the tiles themselves, the transform's scale is left at 1 as in [`Self::bake`].
+    /// The data keeps the orientation it is stored in, see [`Self::scale_oriented`].
+    ///
+    /// ```
+    /// use gridava::hex::shape::{HexShape, ScaleSampling};
+    ///
+    /// // A hexagon of size 1 with its center at (1, 1).
+    /// let my_shape = HexShape::make_hexagon(1, 0, false, |_| 1);
+    ///
+    /// // A hexagon of size 2 with its center at (2, 2).
+    /// let my_shape = my_shape.scale(None, 2.0, ScaleSampling::Nearest);
+    /// ```
+    pub fn scale(self, pivot: Option<Axial>, factor: f32, sampling: ScaleSampling) -> Self {
+        let pivot = pivot.unwrap_or(self.transform.translation);
+        let tiles = self.parent_tiles();
+        let factor = factor as f64;
+        if tiles.is_empty() || !factor.is_normal() || factor < 0.0 {
+            return Self::from_tiles([]);
+        }
+
+        // Bounds of the scaled tile centers, padded by a tile as fractional factors round outwards.
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for coord in tiles.keys() {
+            let q = pivot.q as f64 + (coord.q - pivot.q) as f64 * factor;
+            let r = pivot.r as f64 + (coord.r - pivot.r) as f64 * factor;
+            min = (min.0.min(q), min.1.min(r));
+            max = (max.0.max(q), max.1.max(r));
+        }
+
+        let mut scaled = vec![];
+        for q in floor(min.0) - 1..=floor(max.0) + 1 {
+            for r in floor(min.1) - 1..=floor(max.1) + 1 {
+                // Position of the tile in the source.
+                let fq = pivot.q as f64 + (q - pivot.q) as f64 / factor;
+                let fr = pivot.r as f64 + (r - pivot.r) as f64 / factor;
+
+                let corners = sample_corners(fq, fr);
+                let occupied = corners.iter().filter(|(coord, weight)| {
+                    *weight > SAMPLE_EPSILON && tiles.contains_key(coord)
+                });
+
+                let keep = match sampling {
+                    ScaleSampling::Nearest => {
+                        occupied.clone().count()
+                            == corners
+                                .iter()
+                                .filter(|(_, weight)| *weight > SAMPLE_EPSILON)
+                                .count()
+                    }
+                    ScaleSampling::Majority => {
+                        occupied.clone().map(|(_, weight)| weight).sum::<f64>()
+                            > 0.5 + SAMPLE_EPSILON
+                    }
+                };
+
+                if !keep {
+                    continue;
+                }
+
+                // Take the data of the nearest occupied source tile, the first corner wins a tie.
+                let mut nearest: Option<&(Axial, f64)> = None;
+                for corner in occupied {
+                    if nearest.map_or(true, |best| corner.1 > best.1 + SAMPLE_EPSILON) {
+                        nearest = Some(corner);
+                    }
+                }
+                if let Some((coord, _)) = nearest {
+                    scaled.push((axial!(q, r), tiles[coord].clone()));
+                }
+            }
+        }
+
+        Self::from_tiles(scaled)
+    }
+
+    /// Set a new origin for the shape.
+    pub fn set_origin(&mut self, new_origin: Transform<Axial>) -> &Self {
+        self.transform = new_origin;
+        self
+    }
+
+    /// Apply a shape to a collection
+    ///
+    /// Transforms the coordinates according to the transform. before setting the data into the collection
+    ///
+    /// The data is set as it is stored, see [`Self::apply_oriented`] for data that turns with the shape.
+    ///
+    /// See the colony game example for a usecase.
+    pub fn apply_shape<COL: Collection<Axial, T>>(&self, col: &mut COL) {
+        self.iter().for_each(|(coord, value)| {
+            col.set(coord, value.clone());
+        });
+    }
+
+    /// Work out what applying the shape to a collection would do, without changing it.
+    ///
+    /// See [`Self::apply_shape_with`], the combine closure is not called.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::ReadCollection;
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::{HexShape, MergePolicy};
+    ///
+    /// struct Level {
+    ///     tiles: HashMap<Axial, i32>,
+    /// }
+    ///
+    /// impl ReadCollection<Axial, i32> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&i32> {
+    ///         self.tiles.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let level = Level { tiles: HashMap::from([(axial!(1, 0), 5)]) };
+    ///
+    /// // Highlight the tiles a stamp would skip in the editor.
+    /// let stamp = HexShape::make_line(2, 0, false, |_| 1);
+    /// let report = stamp.preview_shape(&level, &MergePolicy::KeepExisting);
+    /// assert_eq!(report.skipped, vec![axial!(1, 0)]);
+    /// ```
+    pub fn preview_shape<COL: ReadCollection<Axial, T>>(
+        &self,
+        col: &COL,
+        policy: &MergePolicy<T>,
+    ) -> MergeReport {
+        let conflicts: Vec<Axial> = self
+            .iter()
+            .map(|(coord, _)| coord)
+            .filter(|coord| col.get(coord).is_some())
+            .collect();
+        let fail = matches!(policy, MergePolicy::FailOnConflict) && !conflicts.is_empty();
+
+        let (written, skipped) = self.iter().map(|(coord, _)| coord).partition(|coord| {
+            let kept = matches!(policy, MergePolicy::KeepExisting) && col.get(coord).is_some();
+            !(fail || kept)
+        });
+
+        MergeReport {
+            written,
+            skipped,
+            conflicts,
+        }
+    }
+
+    /// Apply a shape to a collection, choosing what happens to tiles that already have data.
+    ///
+    /// Returns a report of the tiles that were written, skipped and that already had data. With
+    /// [`MergePolicy::FailOnConflict`] the collection is only changed if there are no conflicts, so a
+    /// placement can be rejected as a whole.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gridava::core::collection::{Collection, ReadCollection};
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::{HexShape, MergePolicy};
+    ///
+    /// #[derive(Default)]
+    /// struct Level {
+    ///     tiles: HashMap<Axial, i32>,
+    /// }
+    ///
+    /// impl Collection<Axial, i32> for Level {
+    ///     fn set(&mut self, coord: Axial, data: i32) {
+    ///         self.tiles.insert(coord, data);
+    ///     }
+    /// }
+    ///
+    /// impl ReadCollection<Axial, i32> for Level {
+    ///     fn get(&self, coord: &Axial) -> Option<&i32> {
+    ///         self.tiles.get(coord)
+    ///     }
+    /// }
+    ///
+    /// let mut level = Level::default();
+    /// let building = HexShape::make_line(1, 0, false, |_| 1);
+    ///
+    /// // The first building fits, the second overlaps it and is rejected.
+    /// assert!(building.apply_shape_with(&mut level, MergePolicy::FailOnConflict).is_clean());
+    /// let report = building.apply_shape_with(&mut level, MergePolicy::FailOnConflict);
+    /// assert_eq!(report.written.len(), 0);
+    ///
+    /// // Stack heights instead.
+    /// building.apply_shape_with(&mut level, MergePolicy::Combine(Box::new(|a, b| a + b)));
+    /// assert_eq!(level.get(&axial!(0, 0)), Some(&2));
+    /// ```
+    pub fn apply_shape_with<COL>(&self, col: &mut COL, mut policy: MergePolicy<T>) -> MergeReport
+    where
+        COL: Collection<Axial, T> + ReadCollection<Axial, T>,
+    {
+        let report = self.preview_shape(col, &policy);
+        if matches!(policy, MergePolicy::FailOnConflict) && !report.is_clean() {
+            return report;
+        }
+
+        for (coord, value) in self.iter() {
+            let merged = match (col.get(&coord), &mut policy) {
+                (Some(_), MergePolicy::KeepExisting) => continue,
+                (Some(existing), MergePolicy::Combine(combine)) => combine(existing, value),
+                _ => value.clone(),
+            };
+            col.set(coord, merged);
+        }
+
+        report
+    }
+
+    /// Iterate over the shape's tiles in shape local space.
+    ///
+    /// Yields the local coordinate of each occupied tile, for the default storage this is its index in the
+    /// shape's array.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let my_shape = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// for (coord, tile) in my_shape.iter_local() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn iter_local(&self) -> impl Iterator<Item = (Axial, &T)> {
+        self.shape.iter()
+    }
+
+    /// Iterate over the shape's tiles in the parent space.
+    ///
+    /// Yields the coordinate of each occupied tile after applying the shape's transform.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_shape = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// my_shape.translate(axial!(4, 2));
+    ///
+    /// for (coord, tile) in my_shape.iter() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Axial, &T)> {
+        let transform = self.transform;
+        self.iter_local()
+            .map(move |(coord, tile)| (coord.apply_transform(transform), tile))
+    }
+
+    /// Iterate mutably over the shape's tiles in the parent space.
+    ///
+    /// See [`Self::iter`].
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_shape = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// for (coord, tile) in my_shape.iter_mut() {
+    ///     *tile = coord.q;
+    /// }
+    /// ```
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Axial, &mut T)> {
+        let transform = self.transform;
+        self.shape
+            .iter_mut()
+            .map(move |(coord, tile)| (coord.apply_transform(transform), tile))
+    }
+
+    /// Get the tile at a coordinate in the parent space.
+    ///
+    /// The coordinate is brought into shape local space with the inverse of the shape's transform.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_shape = HexShape::make_line(1, 0, false, |coord| coord.q);
+    /// my_shape.translate(axial!(4, 2));
+    ///
+    /// assert_eq!(my_shape.get(axial!(5, 2)), Some(&1));
+    /// assert_eq!(my_shape.get(axial!(1, 0)), None);
+    /// ```
+    pub fn get(&self, coord: Axial) -> Option<&T> {
+        let local = (coord - self.transform.translation).rotate(None, -self.transform.rotation);
+        let local = match self.transform.reflected {
+            true => local.reflect(None, Axes::Q),
+            false => local,
+        };
+
+        self.shape.get(local)
+    }
+
+    /// Check if the shape has a tile at a coordinate in the parent space.
+    ///
+    /// See [`Self::get`].
+    pub fn contains(&self, coord: Axial) -> bool {
+        self.get(coord).is_some()
+    }
+
+    /// Apply the shape's transform to its array.
+    ///
+    /// The tiles are moved to where the transform places them and the array is tightly bound around them.
+    /// Rotation, reflection and scale of the transform are reset, its translation is set to the minimum
+    /// (q, r) of the tiles as the array can only hold positive local coordinates. The data is kept as it is
+    /// stored, see [`Self::bake_oriented`] for data that turns with the shape.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let mut my_shape = HexShape::make_line(2, 0, false, |_| 1);
+    /// my_shape.rotate(None, 1);
+    ///
+    /// // The line now runs down the r axis in its array.
+    /// my_shape.bake();
+    /// ```
+    pub fn bake(&mut self) -> &Self {
+        *self = Self::from_tiles(self.parent_tiles());
+        self
+    }
+
+    // Tiles of the shape keyed by their coordinate in the parent space.
+    fn parent_tiles(&self) -> BTreeMap<Axial, T> {
+        self.iter()
+            .map(|(coord, tile)| (coord, tile.clone()))
+            .collect()
+    }
+
+    /// Find the boundary of the shape in the parent space.
+    ///
+    /// See [`outline`] for how the boundary is described.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let room = HexShape::make_hexagon(2, 0, false, |_| 1);
+    ///
+    /// // Put a wall on every edge around the room.
+    /// let walls = room.outline().edges;
+    /// ```
+    pub fn outline(&self) -> Outline {
+        outline(self.iter().map(|(coord, _)| coord))
+    }
+
+    /// Combine two shapes into one that covers the tiles of both.
+    ///
+    /// Both shapes' transforms are applied, the result is tightly bound with its transform translated to
+    /// its minimum (q, r) in the parent space. The data is kept as it is stored, as in [`Self::bake`].
+    ///
+    /// `merge` is called as `merge(self_tile, other_tile)` to produce the data of a tile both shapes occupy.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let a = HexShape::make_line(2, 0, false, |_| 1);
+    /// let mut b = HexShape::make_line(2, 1, false, |_| 2);
+    /// b.translate(axial!(1, -1));
+    ///
+    /// // Sum the data where the lines cross.
+    /// let cross = a.union(&b, |a, b| a + b);
+    /// ```
+    pub fn union<F>(&self, other: &Self, mut merge: F) -> Self
+    where
+        F: FnMut(&T, &T) -> T,
+    {
+        let mut tiles = self.parent_tiles();
+        for (coord, tile) in other.parent_tiles() {
+            let merged = match tiles.get(&coord) {
+                Some(existing) => merge(existing, &tile),
+                None => tile,
+            };
+            tiles.insert(coord, merged);
+        }
+
+        Self::from_tiles(tiles)
+    }
+
+    /// Create a shape of the tiles both shapes occupy.
+    ///
+    /// See [`Self::union`] for how transforms and `merge` are handled.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let a = HexShape::make_hexagon(2, 0, false, |_| 1);
+    /// let mut b = HexShape::make_hexagon(2, 0, false, |_| 2);
+    /// b.translate(axial!(2, 0));
+    ///
+    /// // Keep the data of a where they overlap.
+    /// let overlap = a.intersection(&b, |a, _| *a);
+    /// ```
+    pub fn intersection<F>(&self, other: &Self, mut merge: F) -> Self
+    where
+        F: FnMut(&T, &T) -> T,
+    {
+        let tiles = self.parent_tiles();
+        Self::from_tiles(
+            other
+                .parent_tiles()
+                .into_iter()
+                .filter_map(|(coord, tile)| {
+                    tiles
+                        .get(&coord)
+                        .map(|existing| (coord, merge(existing, &tile)))
+                })
+                .collect::<Vec<(Axial, T)>>(),
+        )
+    }
+
+    /// Create a shape of the tiles of `self` that `other` does not occupy.
+    ///
+    /// See [`Self::union`] for how transforms are handled.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let a = HexShape::make_hexagon(2, 0, false, |_| 1);
+    /// let mut b = HexShape::make_hexagon(1, 0, false, |_| 2);
+    /// b.translate(axial!(1, 1));
+    ///
+    /// // A ring, a hexagon with its center cut out.
+    /// let ring = a.difference(&b);
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let others = other.parent_tiles();
+        Self::from_tiles(
+            self.parent_tiles()
+                .into_iter()
+                .filter(|(coord, _)| !others.contains_key(coord)),
+        )
+    }
+
+    /// Create a shape of the tiles that only one of the shapes occupy.
+    ///
+    /// See [`Self::union`] for how transforms are handled.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let a = HexShape::make_hexagon(2, 0, false, |_| 1);
+    /// let mut b = HexShape::make_hexagon(2, 0, false, |_| 2);
+    /// b.translate(axial!(2, 0));
+    ///
+    /// let outside_overlap = a.symmetric_difference(&b);
+    /// ```
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut tiles = self.parent_tiles();
+        for (coord, tile) in other.parent_tiles() {
+            if tiles.remove(&coord).is_none() {
+                tiles.insert(coord, tile);
+            }
+        }
+
+        Self::from_tiles(tiles)
+    }
+
+    /// Check if two shapes have a tile at the same coordinate.
+    ///
+    /// Both shapes' transforms are applied, tiles are looked up in place so neither shape is copied.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let building = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// let mut other = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// other.translate(axial!(2, 0));
+    ///
+    /// assert!(building.overlaps(&other));
+    /// ```
+    pub fn overlaps<U: Clone, US: ShapeStorage<U>>(&self, other: &HexShape<U, US>) -> bool {
+        self.iter().any(|(coord, _)| other.contains(coord))
+    }
+
+    /// Find the coordinates in the parent space where both shapes have a tile.
+    ///
+    /// The result is sorted. See [`Self::overlaps`].
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let building = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// let mut other = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// other.translate(axial!(2, 0));
+    ///
+    /// // The tile (2, 1)
+    /// let collisions = building.collisions(&other);
+    /// ```
+    pub fn collisions<U: Clone, US: ShapeStorage<U>>(&self, other: &HexShape<U, US>) -> Vec<Axial> {
+        let mut collisions: Vec<Axial> = self
+            .iter()
+            .map(|(coord, _)| coord)
+            .filter(|coord| other.contains(*coord))
+            .collect();
+        collisions.sort();
+        collisions
+    }
+
+    /// Compute the minimum distance between the tiles of two shapes.
+    ///
+    /// Overlapping shapes have a distance of 0 and touching shapes a distance of 1. None if either shape
+    /// has no tiles.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let building = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// let mut other = HexShape::make_hexagon(1, 0, false, |_| 1);
+    /// other.translate(axial!(5, 0));
+    ///
+    /// // Two tiles of space between the buildings.
+    /// assert_eq!(building.distance(&other), Some(3));
+    /// ```
+    pub fn distance<U: Clone, US: ShapeStorage<U>>(&self, other: &HexShape<U, US>) -> Option<i32> {
+        self.iter()
+            .flat_map(|(a, _)| other.iter().map(move |(b, _)| a.distance(b)))
+            .min()
+    }
+
+    /// Move the shape along a direction until it first touches another shape.
+    ///
+    /// Returns the number of steps to the first overlap along with the coordinates of `other` that are hit,
+    /// sorted. 0 steps means the shapes already overlap, the last free position is one step before contact.
+    /// None if the shapes never meet.
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, HexDirection, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let piece = HexShape::make_line(1, 0, false, |_| 1);
+    /// let mut floor = HexShape::make_line(4, 0, false, |_| 1);
+    /// floor.translate(axial!(-2, 2));
+    ///
+    /// // Drop the piece down the r axis onto the floor, the tiles (0, 2) and (1, 2) of the floor are hit.
+    /// let (steps, hit) = piece.sweep(&floor, HexDirection::FrontRight).unwrap();
+    /// ```
+    pub fn sweep<U: Clone, US: ShapeStorage<U>>(
+        &self,
+        other: &HexShape<U, US>,
+        direction: HexDirection,
+    ) -> Option<(i32, Vec<Axial>)> {
+        let step = direction.to_movement_vector();
+        let mut contact: Option<(i32, Vec<Axial>)> = None;
+
+        for (a, _) in self.iter() {
+            for (b, _) in other.iter() {
+                // The number of steps for a to reach b, if it does.
+                let diff = b - a;
+                let steps = match step.q {
+                    0 => diff.r / step.r,
+                    _ => diff.q / step.q,
+                };
+                if steps < 0 || diff != axial!(step.q * steps, step.r * steps) {
+                    continue;
+                }
+
+                match &mut contact {
+                    Some((best, hit)) if *best == steps => hit.push(b),
+                    Some((best, _)) if *best < steps => {}
+                    _ => contact = Some((steps, vec![b])),
+                }
+            }
+        }
+
+        contact.map(|(steps, mut hit)| {
+            hit.sort();
+            hit.dedup();
+            (steps, hit)
+        })
+    }
+}
+
+/// Build a shape from tiles in the parent space.
+///
+/// The shape is tightly bound around the tiles, its transform is translated to the minimum q and r of the
+/// tiles so the local array starts at (0, 0). If a coordinate is given more than once the last tile is kept.
+///
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::shape::HexShape;
+///
+/// let my_shape = HexShape::from_iter([(axial!(-1, 3), 1), (axial!(1, 2), 2)]);
+///
+/// let my_shape: HexShape<i32> = axial!(0, 0).range(2).into_iter().map(|coord| (coord, coord.q)).collect();
+/// ```
+impl<T: Clone> FromIterator<(Axial, T)> for HexShape<T> {
+    fn from_iter<I>(tiles: I) -> Self
+    where
+        I: IntoIterator<Item = (Axial, T)>,
+    {
+        Self::from_tiles(tiles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::axial;
+
+    struct MockCollection {
+        tiles: HashMap<Axial, i32>,
+    }
+
+    impl Collection<Axial, i32> for MockCollection {
+        fn set(&mut self, coord: Axial, data: i32) {
+            self.tiles.insert(coord, data);
+        }
+    }
+
+    #[test]
+    fn translate() {
+        let mut shape = HexShape::<i32>::new(None, None);
+
+        shape.translate(axial!(4, 6));
+        assert_eq!(shape.transform.translation, axial!(4, 6));
+
+        shape.translate(axial!(2, -3));
+        assert_eq!(shape.transform.translation, axial!(6, 3));
+    }
+
+    #[test]
+    fn rotate_about() {
+        let mut shape = HexShape::<i32>::new(None, None);
+
+        shape.rotate_about(axial!(1, 2), 0);
+        assert_eq!(shape.transform.translation, axial!(0, 0));
+        assert_eq!(shape.transform.rotation, 0);
+
+        shape.rotate_about(axial!(1, 2), 1);
+        assert_eq!(shape.transform.translation, axial!(3, -1));
+        assert_eq!(shape.transform.rotation, 1);
+
+        shape.rotate_about(axial!(1, 2), -1);
+        assert_eq!(shape.transform.translation, axial!(0, 0));
+        assert_eq!(shape.transform.rotation, 0);
+
+        shape.rotate_about(axial!(1, 2), 2);
+        assert_eq!(shape.transform.translation, axial!(4, 1));
+        assert_eq!(shape.transform.rotation, 2);
+
+        shape.rotate_about(axial!(1, 2), -2);
+        assert_eq!(shape.transform.translation, axial!(0, 0));
+        assert_eq!(shape.transform.rotation, 0);
+
+        shape.rotate_about(axial!(1, 2), 3);
+        assert_eq!(shape.transform.translation, axial!(2, 4));
+        assert_eq!(shape.transform.rotation, 3);
+    }
+
+    #[test]
+    fn rotate() {
+        let mut shape = HexShape::<i32>::new(None, None);
 
         shape.rotate(None, 2);
         assert_eq!(shape.transform.translation, axial!(0, 0));
@@ -567,18 +1580,244 @@ mod tests {
         );
     }
 
+    #[test]
+    fn make_ring() {
+        assert_eq!(HexShape::make_ring(0, 1, false, |_| 1).area(), 1);
+        assert_eq!(HexShape::make_ring(2, 1, false, |_| 1).area(), 12);
+        assert_eq!(HexShape::make_ring(3, 2, false, |_| 1).area(), 30);
+        assert_eq!(HexShape::make_ring(2, 0, false, |_| 1).area(), 0);
+        assert_eq!(
+            HexShape::make_ring(2, 5, true, |_| 1),
+            HexShape::make_hexagon(2, 0, true, |_| 1)
+        );
+
+        // The center is empty.
+        let ring = HexShape::make_ring(2, 1, true, |_| 1);
+        assert_eq!(ring.get_hexes().shape(), &[5, 5]);
+        assert_eq!(ring.get(axial!(2, 2)), None);
+        assert_eq!(ring.get(axial!(2, 0)), Some(&1));
+    }
+
+    #[test]
+    fn make_cone() {
+        assert_eq!(
+            HexShape::make_cone(3, 1, 2, true, |_| 1),
+            HexShape::make_triangle(3, 2, true, |_| 1)
+        );
+        assert_eq!(
+            HexShape::make_cone(2, 6, 0, true, |_| 1),
+            HexShape::make_hexagon(2, 0, true, |_| 1)
+        );
+        assert_eq!(HexShape::make_cone(2, 0, 0, true, |_| 1).area(), 0);
+
+        // Each sextant adds a triangle sharing an edge with the last.
+        assert_eq!(HexShape::make_cone(2, 2, 0, false, |_| 1).area(), 9);
+        assert_eq!(HexShape::make_cone(2, 3, 0, false, |_| 1).area(), 12);
+
+        // A half hexagon is its mirror image across its middle.
+        let cone = HexShape::make_cone(3, 3, 1, false, |_| 1);
+        assert_eq!(cone.symmetries(true).len(), 2);
+    }
+
+    #[test]
+    fn make_parallelogram() {
+        assert_eq!(
+            HexShape::make_parallelogram(2, 2, 1, true, |_| 1),
+            HexShape::make_rhombus(2, 1, true, |_| 1)
+        );
+
+        let shape = HexShape::make_parallelogram(3, 1, 0, false, |_| 1);
+        assert_eq!(shape.area(), 8);
+        assert_eq!(shape.get_hexes().shape(), &[4, 2]);
+    }
+
+    #[test]
+    fn make_rectangle() {
+        assert_eq!(HexShape::make_rectangle(0, 3, false, |_| 1).area(), 0);
+
+        let shape = HexShape::make_rectangle(4, 3, false, |coord| coord);
+        assert_eq!(shape.area(), 12);
+
+        // Every other row is shifted back by a tile along q.
+        let rows: Vec<Vec<Axial>> = (0..3)
+            .map(|r| {
+                shape
+                    .iter()
+                    .map(|(coord, _)| coord)
+                    .filter(|coord| coord.r == r)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rows[0],
+            vec![axial!(1, 0), axial!(2, 0), axial!(3, 0), axial!(4, 0)]
+        );
+        assert_eq!(
+            rows[1],
+            vec![axial!(1, 1), axial!(2, 1), axial!(3, 1), axial!(4, 1)]
+        );
+        assert_eq!(
+            rows[2],
+            vec![axial!(0, 2), axial!(1, 2), axial!(2, 2), axial!(3, 2)]
+        );
+    }
+
+    #[test]
+    fn make_star() {
+        assert_eq!(HexShape::make_star(0, false, |_| 1).area(), 1);
+        assert_eq!(HexShape::make_star(1, false, |_| 1).area(), 13);
+        assert_eq!(HexShape::make_star(2, false, |_| 1).area(), 37);
+
+        let star = HexShape::make_star(2, false, |_| 1);
+        assert_eq!(star.symmetries(true).len(), 12);
+        assert_eq!(star.convex_hull().len(), 6);
+    }
+
+    #[test]
+    fn make_irregular_hexagon() {
+        assert_eq!(
+            HexShape::make_irregular_hexagon([2; 6], 0, true, |_| 1),
+            HexShape::make_hexagon(2, 0, true, |_| 1)
+        );
+
+        // A hexagon stretched along q.
+        let shape = HexShape::make_irregular_hexagon([3, 1, 1, 3, 1, 1], 0, false, |_| 1);
+        assert_eq!(shape.area(), 13);
+        assert_eq!(shape.get_hexes().shape(), &[5, 3]);
+        assert_eq!(shape.symmetries(true).len(), 4);
+    }
+
+    #[test]
+    fn mirror() {
+        // A chiral shape, a line with a tile off one side.
+        let mut shape = HexShape::make_line(2, 0, true, |_| 1);
+        shape.get_hexes_mut()[[0, 1]] = Some(1);
+        shape.translate(axial!(2, -1));
+        shape.rotate(None, 1);
+
+        for axes in [Axes::Q, Axes::R, Axes::S] {
+            let mut mirrored = shape.clone();
+            mirrored.mirror(axes);
+
+            let mut expected: Vec<(Axial, i32)> = tiles(&shape)
+                .into_iter()
+                .map(|(coord, tile)| (coord.reflect(Some(axial!(2, -1)), axes), tile))
+                .collect();
+            expected.sort();
+            assert_eq!(tiles(&mirrored), expected);
+
+            // Mirroring twice returns to the original.
+            mirrored.mirror(axes);
+            assert_eq!(tiles(&mirrored), tiles(&shape));
+        }
+
+        // Each rotation of a chiral shape and its mirror image is a different orientation.
+        let mut orientations = BTreeSet::new();
+        for reflected in [false, true] {
+            for rot in 0..6 {
+                let mut oriented = shape.clone();
+                if reflected {
+                    oriented.mirror(Axes::Q);
+                }
+                oriented.rotate(None, rot);
+                orientations.insert(tiles(&oriented));
+            }
+        }
+        assert_eq!(orientations.len(), 12);
+    }
+
     #[test]
     fn scale() {
+        // Regular shapes stay regular at integer factors.
+        let shape =
+            HexShape::make_hexagon(1, 0, false, |_| 1).scale(None, 2.0, ScaleSampling::Nearest);
+        assert_eq!(
+            tiles(&shape),
+            tiles(&HexShape::make_hexagon(2, 0, false, |_| 1))
+        );
+
+        // The factor is baked into the tiles, not kept in the transform.
+        assert_eq!(shape.transform.scale, vector2d!(1.0));
+        assert_eq!(
+            shape
+                .scale(None, 2.0, ScaleSampling::Nearest)
+                .transform
+                .scale,
+            vector2d!(1.0)
+        );
+
+        let shape =
+            HexShape::make_hexagon(2, 0, false, |_| 1).scale(None, 3.0, ScaleSampling::Nearest);
+        assert_eq!(
+            tiles(&shape),
+            tiles(&HexShape::make_hexagon(6, 0, false, |_| 1))
+        );
+
+        for square_bb in [true, false] {
+            let shape = HexShape::make_triangle(2, 1, square_bb, |_| 1).scale(
+                None,
+                2.0,
+                ScaleSampling::Nearest,
+            );
+            assert_eq!(
+                tiles(&shape),
+                tiles(&HexShape::make_triangle(4, 1, false, |_| 1))
+            );
+        }
+
+        // Scaling about a pivot keeps the pivot in place.
+        let mut line = HexShape::make_line(2, 0, false, |coord| coord.q);
+        line.translate(axial!(1, 1));
+        let shape = line
+            .clone()
+            .scale(Some(axial!(3, 1)), 2.0, ScaleSampling::Nearest);
+        assert_eq!(
+            tiles(&shape),
+            vec![
+                (axial!(-1, 1), 0),
+                (axial!(0, 1), 0),
+                (axial!(1, 1), 1),
+                (axial!(2, 1), 1),
+                (axial!(3, 1), 2)
+            ]
+        );
+
+        // Scaling back down returns the original.
+        let shape = HexShape::make_rhombus(2, 0, true, |coord| coord.q * 3 + coord.r);
         assert_eq!(
-            HexShape::make_rhombus(1, 0, true, |_| 1).scale(vector2d!(2.0, 2.0)),
-            HexShape::make_rhombus(3, 0, true, |_| 1)
+            tiles(
+                &shape
+                    .clone()
+                    .scale(None, 2.0, ScaleSampling::Nearest)
+                    .scale(None, 0.5, ScaleSampling::Nearest)
+            ),
+            tiles(&shape)
+        );
+
+        // Majority fills in where most of the source is occupied.
+        let shape = HexShape::make_hexagon(1, 0, false, |_| 1);
+        assert!(
+            shape
+                .clone()
+                .scale(None, 1.5, ScaleSampling::Majority)
+                .get_hexes()
+                .iter()
+                .flatten()
+                .count()
+                >= shape
+                    .scale(None, 1.5, ScaleSampling::Nearest)
+                    .get_hexes()
+                    .iter()
+                    .flatten()
+                    .count()
         );
 
         assert_eq!(
-            HexShape::make_rhombus(1, 0, true, |_| 1)
-                .scale(vector2d!(2.0, 2.0))
-                .scale(vector2d!(0.5, 0.5)),
-            HexShape::make_rhombus(1, 0, true, |_| 1)
+            HexShape::<i32>::new(None, None)
+                .scale(None, 2.0, ScaleSampling::Nearest)
+                .get_hexes()
+                .len(),
+            0
         );
     }
 
@@ -631,5 +1870,390 @@ mod tests {
         )
     }
 
+    // Tiles of a shape in parent space, for comparing shapes with different bounds.
+    fn tiles(shape: &HexShape<i32>) -> Vec<(Axial, i32)> {
+        shape.parent_tiles().into_iter().collect()
+    }
+
+    impl ReadCollection<Axial, i32> for MockCollection {
+        fn get(&self, coord: &Axial) -> Option<&i32> {
+            self.tiles.get(coord)
+        }
+    }
+
+    #[test]
+    fn extract() {
+        let mut col = MockCollection {
+            tiles: Default::default(),
+        };
+        HexShape::make_hexagon(2, 0, false, |coord| coord.q).apply_shape(&mut col);
+
+        // Copying the whole region round trips.
+        let shape = HexShape::extract(&col, axial!(2, 2).range(3));
+        assert_eq!(
+            tiles(&shape),
+            tiles(&HexShape::make_hexagon(2, 0, false, |coord| coord.q))
+        );
+
+        // Coordinates outside of the collection are skipped.
+        let shape = HexShape::extract(&col, axial!(4, 2).range(1));
+        assert_eq!(
+            tiles(&shape),
+            vec![
+                (axial!(3, 2), 3),
+                (axial!(3, 3), 3),
+                (axial!(4, 1), 4),
+                (axial!(4, 2), 4)
+            ]
+        );
+
+        // Copy under a mask.
+        let mut mask = HexShape::make_line(1, 1, false, |_| ());
+        mask.translate(axial!(2, 2));
+        let shape = HexShape::extract(&col, mask.iter().map(|(coord, _)| coord));
+        assert_eq!(tiles(&shape), vec![(axial!(2, 2), 2), (axial!(2, 3), 2)]);
+
+        // Only copy tiles matching a predicate.
+        let shape = HexShape::extract_where(&col, axial!(2, 2).range(2), |_, tile| *tile == 0);
+        assert_eq!(
+            tiles(&shape),
+            vec![(axial!(0, 2), 0), (axial!(0, 3), 0), (axial!(0, 4), 0)]
+        );
+        assert_eq!(shape.transform, transform!(axial!(0, 2)));
+    }
+
+    #[test]
+    fn into_storage() {
+        use crate::hex::storage::{RunLengthStorage, SparseStorage};
+
+        let mut shape = HexShape::make_hexagon(2, 0, true, |coord| coord.q * 10 + coord.r);
+        shape.get_hexes_mut()[[2, 2]] = None;
+        shape.translate(axial!(3, -1));
+        shape.rotate(None, 2);
+
+        // Round trips keep the array and transform.
+        let sparse = shape.clone().into_storage::<SparseStorage<i32>>();
+        let runs = shape.clone().into_storage::<RunLengthStorage<i32>>();
+        assert_eq!(sparse.clone().into_storage(), shape);
+        assert_eq!(runs.clone().into_storage(), shape);
+        assert_eq!(sparse.clone().into_storage::<RunLengthStorage<i32>>(), runs);
+
+        // Every storage places the same tiles.
+        let dense: Vec<(Axial, i32)> = shape.iter().map(|(c, t)| (c, *t)).collect();
+        let sparse_tiles: Vec<(Axial, i32)> = sparse.iter().map(|(c, t)| (c, *t)).collect();
+        let run_tiles: Vec<(Axial, i32)> = runs.iter().map(|(c, t)| (c, *t)).collect();
+        assert_eq!(dense, sparse_tiles);
+        assert_eq!(dense, run_tiles);
+        assert_eq!(runs.get(axial!(1, 0)), shape.get(axial!(1, 0)));
+
+        // Shape operations work the same on any storage.
+        let mut baked = runs.clone();
+        baked.bake();
+        let mut expected = shape.clone();
+        expected.bake();
+        assert_eq!(baked.into_storage(), expected);
+        assert_eq!(
+            runs.union(&runs, |a, _| *a).into_storage(),
+            shape.union(&shape, |a, _| *a)
+        );
+        assert_eq!(sparse.canonical_hash(true), shape.canonical_hash(true));
+        assert!(sparse.overlaps(&shape));
+
+        let built: HexShape<i32, SparseStorage<i32>> = HexShape::from_tiles(shape.parent_tiles());
+        assert_eq!(
+            built.into_storage(),
+            HexShape::from_iter(shape.parent_tiles())
+        );
+    }
+
+    #[test]
+    fn apply_shape_with() {
+        let mut col = MockCollection {
+            tiles: HashMap::from([(axial!(1, 0), 5)]),
+        };
+        let line = HexShape::make_line(2, 0, false, |_| 1);
+        let all = vec![axial!(0, 0), axial!(1, 0), axial!(2, 0)];
+
+        // Nothing is written when a conflict fails the placement.
+        let report = line.apply_shape_with(&mut col, MergePolicy::FailOnConflict);
+        assert!(!report.is_clean());
+        assert_eq!(report.written, vec![]);
+        assert_eq!(report.skipped, all);
+        assert_eq!(report.conflicts, vec![axial!(1, 0)]);
+        assert_eq!(col.tiles.len(), 1);
+
+        // Existing data is kept.
+        let report = line.apply_shape_with(&mut col, MergePolicy::KeepExisting);
+        assert_eq!(report.written, vec![axial!(0, 0), axial!(2, 0)]);
+        assert_eq!(report.skipped, vec![axial!(1, 0)]);
+        assert_eq!(col.tiles[&axial!(1, 0)], 5);
+        assert_eq!(col.tiles[&axial!(0, 0)], 1);
+
+        // Existing data is combined with the shape's.
+        let report =
+            line.apply_shape_with(&mut col, MergePolicy::Combine(Box::new(|a, b| a * 10 + b)));
+        assert_eq!(report.written, all);
+        assert_eq!(report.conflicts, all);
+        assert_eq!(col.tiles[&axial!(1, 0)], 51);
+        assert_eq!(col.tiles[&axial!(0, 0)], 11);
+
+        // Existing data is replaced, the preview agrees with what was done.
+        let preview = line.preview_shape(&col, &MergePolicy::Overwrite);
+        let report = line.apply_shape_with(&mut col, MergePolicy::Overwrite);
+        assert_eq!(preview, report);
+        assert_eq!(report.written, all);
+        assert!(col.tiles.values().all(|tile| *tile == 1));
+
+        // A placement on empty tiles succeeds.
+        let mut moved = line.clone();
+        moved.translate(axial!(0, 1));
+        let report = moved.apply_shape_with(&mut col, MergePolicy::FailOnConflict);
+        assert!(report.is_clean());
+        assert_eq!(report.written.len(), 3);
+        assert_eq!(col.tiles.len(), 6);
+    }
+
+    #[test]
+    fn get() {
+        let mut shape = HexShape::make_line(2, 0, true, |coord| coord.q);
+        shape.get_hexes_mut()[[0, 1]] = Some(9);
+        shape.mirror(Axes::R);
+        shape.rotate(None, 2);
+        shape.translate(axial!(-3, 1));
+
+        for (coord, tile) in shape.iter() {
+            assert_eq!(shape.get(coord), Some(tile));
+            assert!(shape.contains(coord));
+        }
+        assert_eq!(
+            axial!(0, 0)
+                .range(6)
+                .into_iter()
+                .filter(|coord| shape.contains(*coord))
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn overlaps() {
+        let a = HexShape::make_hexagon(1, 0, false, |_| 1);
+        let mut b = HexShape::make_rhombus(1, 0, false, |_| 'b');
+        b.rotate(None, 3);
+        b.translate(axial!(3, 2));
+
+        assert!(a.overlaps(&b));
+        assert!(b.overlaps(&a));
+        assert_eq!(a.collisions(&b), vec![axial!(2, 1)]);
+        assert_eq!(a.collisions(&b), b.collisions(&a));
+        assert_eq!(a.distance(&b), Some(0));
+
+        b.translate(axial!(1, 0));
+        assert!(!a.overlaps(&b));
+        assert!(a.collisions(&b).is_empty());
+        assert_eq!(a.distance(&b), Some(1));
+
+        b.translate(axial!(3, 0));
+        assert_eq!(a.distance(&b), Some(4));
+        assert_eq!(a.distance(&HexShape::<i32>::new(None, None)), None);
+    }
+
+    #[test]
+    fn sweep() {
+        let piece = HexShape::make_line(1, 0, false, |_| 1);
+        let mut floor = HexShape::make_line(4, 0, false, |_| 1);
+        floor.translate(axial!(-2, 2));
+
+        assert_eq!(
+            piece.sweep(&floor, HexDirection::FrontRight),
+            Some((2, vec![axial!(0, 2), axial!(1, 2)]))
+        );
+
+        // Moving the piece by the steps makes it overlap, one less and it does not.
+        let mut moved = piece.clone();
+        moved.translate(axial!(0, 1));
+        assert!(!moved.overlaps(&floor));
+        moved.translate(axial!(0, 1));
+        assert_eq!(moved.collisions(&floor), vec![axial!(0, 2), axial!(1, 2)]);
+
+        // Moving away or alongside never touches.
+        assert_eq!(piece.sweep(&floor, HexDirection::BackLeft), None);
+        assert_eq!(piece.sweep(&floor, HexDirection::Back), None);
+        assert_eq!(
+            piece.sweep(&floor, HexDirection::BackRight),
+            Some((2, vec![axial!(-2, 2), axial!(-1, 2)]))
+        );
+
+        // Already overlapping.
+        assert_eq!(
+            floor
+                .sweep(&floor, HexDirection::Front)
+                .map(|(steps, _)| steps),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn iter() {
+        let mut shape = HexShape::make_line(1, 0, false, |coord| coord.q);
+        shape.rotate(None, 1);
+        shape.translate(axial!(2, 1));
+
+        assert_eq!(
+            shape.iter_local().collect::<Vec<_>>(),
+            vec![(axial!(0, 0), &0), (axial!(1, 0), &1)]
+        );
+        assert_eq!(
+            shape.iter().collect::<Vec<_>>(),
+            vec![(axial!(2, 1), &0), (axial!(2, 2), &1)]
+        );
+
+        for (coord, tile) in shape.iter_mut() {
+            *tile = coord.r;
+        }
+        assert_eq!(
+            shape.iter().collect::<Vec<_>>(),
+            vec![(axial!(2, 1), &1), (axial!(2, 2), &2)]
+        );
+
+        assert_eq!(HexShape::<i32>::new(None, None).iter().count(), 0);
+    }
+
+    #[test]
+    fn bake() {
+        let mut shape = HexShape::make_line(2, 0, true, |coord| coord.q);
+        shape.rotate(None, 2);
+        shape.mirror(Axes::S);
+        shape.translate(axial!(-1, 3));
+        let expected = tiles(&shape);
+
+        shape.bake();
+        assert_eq!(tiles(&shape), expected);
+        assert_eq!(shape.transform.rotation, 0);
+        assert!(!shape.transform.reflected);
+
+        // Tightly bound with the transform at the minimum coordinate.
+        let qs: Vec<i32> = expected.iter().map(|(coord, _)| coord.q).collect();
+        let rs: Vec<i32> = expected.iter().map(|(coord, _)| coord.r).collect();
+        let (min_q, max_q) = (*qs.iter().min().unwrap(), *qs.iter().max().unwrap());
+        let (min_r, max_r) = (*rs.iter().min().unwrap(), *rs.iter().max().unwrap());
+        assert_eq!(shape.transform, transform!(axial!(min_q, min_r)));
+        assert_eq!(
+            shape.get_hexes().shape(),
+            &[(max_q - min_q + 1) as usize, (max_r - min_r + 1) as usize]
+        );
+    }
+
+    #[test]
+    fn from_iter() {
+        assert_eq!(HexShape::<i32>::from_iter([]), HexShape::new(None, None));
+
+        let shape = HexShape::from_iter([(axial!(-1, 3), 1), (axial!(1, 2), 2)]);
+        assert_eq!(shape.transform, transform!(axial!(-1, 2)));
+        assert_eq!(
+            shape.get_hexes(),
+            array![[None, Some(1)], [None, None], [Some(2), None]]
+        );
+        assert_eq!(tiles(&shape), vec![(axial!(-1, 3), 1), (axial!(1, 2), 2)]);
+    }
+
+    #[test]
+    fn union() {
+        let a = HexShape::make_line(2, 0, false, |_| 1);
+        let mut b = HexShape::make_line(2, 1, false, |_| 2);
+        b.translate(axial!(1, -1));
+
+        let shape = a.union(&b, |a, b| a + b);
+        assert_eq!(
+            tiles(&shape),
+            vec![
+                (axial!(0, 0), 1),
+                (axial!(1, -1), 2),
+                (axial!(1, 0), 3),
+                (axial!(1, 1), 2),
+                (axial!(2, 0), 1)
+            ]
+        );
+        assert_eq!(shape.transform, transform!(axial!(0, -1)));
+        assert_eq!(shape.get_hexes().shape(), &[3, 3]);
+
+        // Rotation of the other shape is honoured.
+        let mut c = HexShape::make_line(1, 0, false, |_| 2);
+        c.rotate(None, 3);
+        assert_eq!(
+            tiles(&a.union(&c, |a, _| *a)),
+            vec![
+                (axial!(-1, 0), 2),
+                (axial!(0, 0), 1),
+                (axial!(1, 0), 1),
+                (axial!(2, 0), 1)
+            ]
+        );
+
+        // Union with nothing is the same tiles.
+        assert_eq!(
+            tiles(&a.union(&HexShape::new(None, None), |a, _| *a)),
+            tiles(&a)
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        let a = HexShape::make_hexagon(1, 0, false, |_| 1);
+        let mut b = HexShape::make_hexagon(1, 0, false, |_| 2);
+        b.translate(axial!(1, 0));
+
+        let shape = a.intersection(&b, |a, b| a * 10 + b);
+        assert_eq!(
+            tiles(&shape),
+            vec![
+                (axial!(1, 1), 12),
+                (axial!(1, 2), 12),
+                (axial!(2, 0), 12),
+                (axial!(2, 1), 12)
+            ]
+        );
+        assert_eq!(shape.get_hexes().shape(), &[2, 3]);
+
+        b.translate(axial!(5, 0));
+        assert_eq!(a.intersection(&b, |a, _| *a), HexShape::new(None, None));
+    }
+
+    #[test]
+    fn difference() {
+        let a = HexShape::make_hexagon(1, 0, false, |_| 1);
+        let mut b = HexShape::make_line(0, 0, false, |_| 2);
+        b.translate(axial!(1, 1));
+
+        let ring = a.difference(&b);
+        assert_eq!(ring.get_hexes().iter().flatten().count(), 6);
+        assert!(!tiles(&ring).contains(&(axial!(1, 1), 1)));
+
+        assert_eq!(a.difference(&a), HexShape::new(None, None));
+    }
+
+    #[test]
+    fn symmetric_difference() {
+        let a = HexShape::make_line(2, 0, false, |_| 1);
+        let mut b = HexShape::make_line(2, 0, false, |_| 2);
+        b.translate(axial!(1, 0));
+
+        assert_eq!(
+            tiles(&a.symmetric_difference(&b)),
+            vec![(axial!(0, 0), 1), (axial!(3, 0), 2)]
+        );
+        assert_eq!(a.symmetric_difference(&a), HexShape::new(None, None));
+    }
+
+    #[test]
+    fn outline() {
+        let mut shape = HexShape::make_line(1, 0, false, |_| 1);
+        shape.translate(axial!(2, 3));
+        assert_eq!(
+            shape.outline(),
+            super::outline([axial!(2, 3), axial!(3, 3)])
+        );
+    }
+
     // TODO: scale, get_hexes
 }
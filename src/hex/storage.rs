@@ -0,0 +1,343 @@
+//! Storage backends for the tiles of a [`HexShape`](super::shape::HexShape).
+//!
+//! A shape keeps its tiles in shape local space, where coordinates are never negative. The default storage
+//! is a dense array covering the shape's bounding box, which is fast to index but wastes memory on long
+//! lines, rings and sparse stamps. [`SparseStorage`] and [`RunLengthStorage`] only store occupied tiles.
+//!
+//! Shapes can move between storages with [`HexShape::into_storage`](super::shape::HexShape::into_storage).
+
+use crate::lib::*;
+
+use crate::axial;
+
+use super::coordinate::Axial;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The storage of a shape's tiles in shape local space.
+pub trait ShapeStorage<T>: Clone {
+    /// Create the storage from tiles.
+    ///
+    /// `dim` is the size of the local bounding box, every tile's q is less than `dim.0` and its r is less
+    /// than `dim.1`. Storage that keeps an array uses it as the size of the array. If a coordinate is given
+    /// more than once the last tile is kept.
+    fn from_tiles<I>(dim: (usize, usize), tiles: I) -> Self
+    where
+        I: IntoIterator<Item = (Axial, T)>;
+
+    /// Get the size of the local bounding box, see [`Self::from_tiles`].
+    fn dim(&self) -> (usize, usize);
+
+    /// Get the tile at a local coordinate.
+    fn get(&self, coord: Axial) -> Option<&T>;
+
+    /// Get the tile at a local coordinate mutably.
+    fn get_mut(&mut self, coord: Axial) -> Option<&mut T>;
+
+    /// Iterate over the occupied tiles, sorted by q then r.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Axial, &'a T)>
+    where
+        T: 'a;
+
+    /// Iterate mutably over the occupied tiles, sorted by q then r.
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (Axial, &'a mut T)>
+    where
+        T: 'a;
+}
+
+// Array index of a local coordinate, None if it is negative.
+fn index(coord: Axial) -> Option<(usize, usize)> {
+    match coord.q < 0 || coord.r < 0 {
+        true => None,
+        false => Some((coord.q as usize, coord.r as usize)),
+    }
+}
+
+/// A dense array covering the bounding box, indexed by (q, r).
+impl<T: Clone> ShapeStorage<T> for Array2<Option<T>> {
+    fn from_tiles<I>(dim: (usize, usize), tiles: I) -> Self
+    where
+        I: IntoIterator<Item = (Axial, T)>,
+    {
+        let mut arr = Array::from_shape_simple_fn(dim, || None);
+        for (coord, tile) in tiles {
+            arr[[coord.q as usize, coord.r as usize]] = Some(tile);
+        }
+        arr
+    }
+
+    fn dim(&self) -> (usize, usize) {
+        (self.nrows(), self.ncols())
+    }
+
+    fn get(&self, coord: Axial) -> Option<&T> {
+        index(coord)
+            .and_then(|idx| self.get(idx))
+            .and_then(|tile| tile.as_ref())
+    }
+
+    fn get_mut(&mut self, coord: Axial) -> Option<&mut T> {
+        index(coord)
+            .and_then(|idx| self.get_mut(idx))
+            .and_then(|tile| tile.as_mut())
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Axial, &'a T)>
+    where
+        T: 'a,
+    {
+        self.indexed_iter().filter_map(|((q, r), tile)| {
+            tile.as_ref().map(|tile| (axial!(q as i32, r as i32), tile))
+        })
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (Axial, &'a mut T)>
+    where
+        T: 'a,
+    {
+        self.indexed_iter_mut().filter_map(|((q, r), tile)| {
+            tile.as_mut().map(|tile| (axial!(q as i32, r as i32), tile))
+        })
+    }
+}
+
+/// A sorted list of the occupied tiles.
+///
+/// Memory is proportional to the number of tiles, lookups are a binary search.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct SparseStorage<T> {
+    /// Size of the local bounding box.
+    dim: (usize, usize),
+    /// Occupied tiles sorted by coordinate.
+    tiles: Vec<(Axial, T)>,
+}
+
+impl<T> Default for SparseStorage<T> {
+    fn default() -> Self {
+        Self {
+            dim: (0, 0),
+            tiles: vec![],
+        }
+    }
+}
+
+impl<T> SparseStorage<T> {
+    fn position(&self, coord: Axial) -> Option<usize> {
+        self.tiles
+            .binary_search_by_key(&coord, |(coord, _)| *coord)
+            .ok()
+    }
+}
+
+impl<T: Clone> ShapeStorage<T> for SparseStorage<T> {
+    fn from_tiles<I>(dim: (usize, usize), tiles: I) -> Self
+    where
+        I: IntoIterator<Item = (Axial, T)>,
+    {
+        // Sorted, with the last tile kept when a coordinate is given twice.
+        let tiles: BTreeMap<Axial, T> = tiles.into_iter().collect();
+        Self {
+            dim,
+            tiles: tiles.into_iter().collect(),
+        }
+    }
+
+    fn dim(&self) -> (usize, usize) {
+        self.dim
+    }
+
+    fn get(&self, coord: Axial) -> Option<&T> {
+        self.position(coord).map(|idx| &self.tiles[idx].1)
+    }
+
+    fn get_mut(&mut self, coord: Axial) -> Option<&mut T> {
+        self.position(coord).map(|idx| &mut self.tiles[idx].1)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Axial, &'a T)>
+    where
+        T: 'a,
+    {
+        self.tiles.iter().map(|(coord, tile)| (*coord, tile))
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (Axial, &'a mut T)>
+    where
+        T: 'a,
+    {
+        self.tiles.iter_mut().map(|(coord, tile)| (*coord, tile))
+    }
+}
+
+/// A run of consecutive tiles along the r axis.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+struct Run<T> {
+    /// Coordinate of the first tile.
+    start: Axial,
+    /// Tiles from `start` increasing in r.
+    tiles: Vec<T>,
+}
+
+/// Runs of consecutive tiles in each row of the array.
+///
+/// Each row of a fixed q is stored as runs of occupied tiles, memory is proportional to the number of tiles
+/// plus the number of gaps. Suits filled shapes that are far from square, like long lines and rings, with
+/// lookups a binary search over the runs.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct RunLengthStorage<T> {
+    /// Size of the local bounding box.
+    dim: (usize, usize),
+    /// Runs sorted by their start.
+    runs: Vec<Run<T>>,
+}
+
+impl<T> Default for RunLengthStorage<T> {
+    fn default() -> Self {
+        Self {
+            dim: (0, 0),
+            runs: vec![],
+        }
+    }
+}
+
+impl<T> RunLengthStorage<T> {
+    // Index of the run and the tile in it holding a coordinate.
+    fn position(&self, coord: Axial) -> Option<(usize, usize)> {
+        let idx = self
+            .runs
+            .partition_point(|run| run.start <= coord)
+            .checked_sub(1)?;
+        let run = &self.runs[idx];
+        let offset = coord.r - run.start.r;
+        (run.start.q == coord.q && offset >= 0 && (offset as usize) < run.tiles.len())
+            .then_some((idx, offset as usize))
+    }
+}
+
+impl<T: Clone> ShapeStorage<T> for RunLengthStorage<T> {
+    fn from_tiles<I>(dim: (usize, usize), tiles: I) -> Self
+    where
+        I: IntoIterator<Item = (Axial, T)>,
+    {
+        let tiles: BTreeMap<Axial, T> = tiles.into_iter().collect();
+
+        let mut runs: Vec<Run<T>> = vec![];
+        for (coord, tile) in tiles {
+            match runs.last_mut() {
+                Some(run)
+                    if run.start.q == coord.q
+                        && run.start.r + run.tiles.len() as i32 == coord.r =>
+                {
+                    run.tiles.push(tile)
+                }
+                _ => runs.push(Run {
+                    start: coord,
+                    tiles: vec![tile],
+                }),
+            }
+        }
+
+        Self { dim, runs }
+    }
+
+    fn dim(&self) -> (usize, usize) {
+        self.dim
+    }
+
+    fn get(&self, coord: Axial) -> Option<&T> {
+        self.position(coord)
+            .map(|(run, offset)| &self.runs[run].tiles[offset])
+    }
+
+    fn get_mut(&mut self, coord: Axial) -> Option<&mut T> {
+        self.position(coord)
+            .map(|(run, offset)| &mut self.runs[run].tiles[offset])
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (Axial, &'a T)>
+    where
+        T: 'a,
+    {
+        self.runs.iter().flat_map(|run| {
+            run.tiles
+                .iter()
+                .enumerate()
+                .map(|(offset, tile)| (run.start + axial!(0, offset as i32), tile))
+        })
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (Axial, &'a mut T)>
+    where
+        T: 'a,
+    {
+        self.runs.iter_mut().flat_map(|run| {
+            let start = run.start;
+            run.tiles
+                .iter_mut()
+                .enumerate()
+                .map(move |(offset, tile)| (start + axial!(0, offset as i32), tile))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A sparse set of tiles with gaps in its rows.
+    fn tiles() -> Vec<(Axial, i32)> {
+        vec![
+            (axial!(0, 0), 1),
+            (axial!(0, 1), 2),
+            (axial!(0, 3), 3),
+            (axial!(2, 1), 4),
+            (axial!(2, 2), 5),
+        ]
+    }
+
+    fn check<S: ShapeStorage<i32>>() {
+        let mut storage = S::from_tiles((3, 4), tiles());
+        assert_eq!(storage.dim(), (3, 4));
+
+        let collected: Vec<(Axial, i32)> = storage.iter().map(|(c, t)| (c, *t)).collect();
+        assert_eq!(collected, tiles());
+
+        assert_eq!(storage.get(axial!(0, 3)), Some(&3));
+        assert_eq!(storage.get(axial!(0, 2)), None);
+        assert_eq!(storage.get(axial!(1, 1)), None);
+        assert_eq!(storage.get(axial!(-1, 0)), None);
+        assert_eq!(storage.get(axial!(5, 5)), None);
+
+        *storage.get_mut(axial!(2, 2)).unwrap() = 9;
+        assert_eq!(storage.get(axial!(2, 2)), Some(&9));
+        storage.iter_mut().for_each(|(_, tile)| *tile += 1);
+        assert_eq!(storage.get(axial!(0, 0)), Some(&2));
+
+        let empty = S::from_tiles((0, 0), []);
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.dim(), (0, 0));
+    }
+
+    #[test]
+    fn dense() {
+        check::<Array2<Option<i32>>>();
+    }
+
+    #[test]
+    fn sparse() {
+        check::<SparseStorage<i32>>();
+    }
+
+    #[test]
+    fn run_length() {
+        check::<RunLengthStorage<i32>>();
+
+        // Consecutive tiles in a row share a run.
+        let storage = RunLengthStorage::from_tiles((3, 4), tiles());
+        assert_eq!(storage.runs.len(), 3);
+    }
+}
@@ -0,0 +1,356 @@
+//! Canonical forms and symmetries of shapes.
+//!
+//! Shapes are compared by the coordinates of their tiles in the parent space, tile data is carried along but
+//! never compared.
+
+use crate::lib::*;
+
+use crate::{
+    axial,
+    core::transform::{Transform, Vector2D},
+    transform, vector2d,
+};
+
+use super::{coordinate::Axial, shape::HexShape, storage::ShapeStorage};
+
+// Every rotation and reflection of a hexagon, the 6 rotations without reflection come first.
+pub(crate) fn orientations(reflections: bool) -> impl Iterator<Item = Transform<Axial>> {
+    let reflected: &[bool] = match reflections {
+        true => &[false, true],
+        false => &[false],
+    };
+
+    reflected.iter().flat_map(|reflected| {
+        (0..6).map(move |rotation| transform!(axial!(0, 0), rotation, vector2d!(1.0), *reflected))
+    })
+}
+
+// Sorted coordinates moved so the minimum q and r are 0, along with the offset that was removed.
+pub(crate) fn normalize(mut coords: Vec<Axial>) -> (Vec<Axial>, Axial) {
+    let min = coords.iter().fold(None, |min: Option<Axial>, coord| {
+        Some(match min {
+            Some(min) => axial!(min.q.min(coord.q), min.r.min(coord.r)),
+            None => *coord,
+        })
+    });
+    let min = min.unwrap_or_default();
+
+    coords.iter_mut().for_each(|coord| *coord -= min);
+    coords.sort();
+    (coords, min)
+}
+
+/// Canonical forms and symmetries.
+///
+/// `reflections` chooses if mirror images are treated as the same shape, with it every one of the 12
+/// orientations of a hexagon is considered, otherwise only the 6 rotations.
+impl<T: Clone, S: ShapeStorage<T>> HexShape<T, S> {
+    // Parent space coordinates of the tiles after applying an orientation.
+    fn oriented_coords(&self, orientation: Transform<Axial>) -> Vec<Axial> {
+        self.iter()
+            .map(|(coord, _)| coord.apply_transform(orientation))
+            .collect()
+    }
+
+    /// Find the transform that takes the shape to its canonical form.
+    ///
+    /// The transform is applied to the tiles in the parent space, see [`Self::canonical`].
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let transform = HexShape::make_triangle(2, 3, false, |_| 1).canonical_transform(true);
+    /// ```
+    pub fn canonical_transform(&self, reflections: bool) -> Transform<Axial> {
+        let mut best: Option<(Vec<Axial>, Transform<Axial>)> = None;
+
+        for orientation in orientations(reflections) {
+            let (coords, min) = normalize(self.oriented_coords(orientation));
+            if best.as_ref().map_or(true, |(best, _)| coords < *best) {
+                let mut transform = orientation;
+                transform.translation = -min;
+                best = Some((coords, transform));
+            }
+        }
+
+        best.map(|(_, transform)| transform).unwrap_or_default()
+    }
+
+    /// Reduce the shape to its canonical form.
+    ///
+    /// Every shape that is the same up to translation and rotation, and reflection if `reflections` is set,
+    /// has the same canonical form. The canonical form is the orientation with the least sorted tile
+    /// coordinates, translated so its minimum q and r are 0. The result has an identity transform, the data
+    /// is kept as it is stored rather than oriented, see [`crate::hex::orientable`].
+    ///
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let a = HexShape::make_line(2, 0, false, |_| 1);
+    /// let mut b = HexShape::make_line(2, 2, false, |_| 1);
+    /// b.translate(axial!(5, -3));
+    ///
+    /// assert_eq!(a.canonical(false), b.canonical(false));
+    /// ```
+    pub fn canonical(&self, reflections: bool) -> Self {
+        let transform = self.canonical_transform(reflections);
+        Self::from_tiles(
+            self.iter()
+                .map(|(coord, tile)| (coord.apply_transform(transform), tile.clone())),
+        )
+    }
+
+    /// Get the sorted tile coordinates of the canonical form.
+    ///
+    /// Useful as a key to deduplicate shapes in a map or set.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let key = HexShape::make_triangle(2, 0, false, |_| 1).canonical_key(true);
+    /// ```
+    pub fn canonical_key(&self, reflections: bool) -> Vec<Axial> {
+        let (coords, _) = normalize(self.oriented_coords(self.canonical_transform(reflections)));
+        coords
+    }
+
+    /// Compute a stable hash of the canonical form.
+    ///
+    /// The hash is FNV-1a over the canonical key, it does not change between runs, platforms or versions of
+    /// Rust so it can be stored or sent over a network.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// let a = HexShape::make_triangle(2, 0, false, |_| 1);
+    /// let b = HexShape::make_triangle(2, 1, false, |_| 1);
+    ///
+    /// assert_eq!(a.canonical_hash(false), b.canonical_hash(false));
+    /// ```
+    pub fn canonical_hash(&self, reflections: bool) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        self.canonical_key(reflections)
+            .iter()
+            .flat_map(|coord| {
+                let mut bytes = [0; 8];
+                bytes[..4].copy_from_slice(&coord.q.to_le_bytes());
+                bytes[4..].copy_from_slice(&coord.r.to_le_bytes());
+                bytes
+            })
+            .fold(OFFSET_BASIS, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(PRIME)
+            })
+    }
+
+    /// Find the transforms that map the shape onto itself.
+    ///
+    /// Each transform is a rotation, possibly a reflection, and the translation that puts the tiles back in
+    /// place. They are applied to the tiles in the parent space. The identity is always included.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // A hexagon maps onto itself under every rotation and reflection.
+    /// assert_eq!(HexShape::make_hexagon(1, 0, false, |_| 1).symmetries(true).len(), 12);
+    ///
+    /// // A line only under a half turn.
+    /// assert_eq!(HexShape::make_line(3, 0, false, |_| 1).symmetries(false).len(), 2);
+    /// ```
+    pub fn symmetries(&self, reflections: bool) -> Vec<Transform<Axial>> {
+        let (coords, min) = normalize(self.oriented_coords(Transform::default()));
+
+        orientations(reflections)
+            .filter_map(|orientation| {
+                let (oriented, oriented_min) = normalize(self.oriented_coords(orientation));
+                (oriented == coords).then(|| {
+                    let mut transform = orientation;
+                    transform.translation = min - oriented_min;
+                    transform
+                })
+            })
+            .collect()
+    }
+
+    /// Find the orientations that give distinct shapes.
+    ///
+    /// One rotation, possibly with a reflection, for every distinct orientation of the shape. Orientations that
+    /// only differ by a symmetry of the shape are skipped. The transforms have no translation.
+    ///
+    /// ```
+    /// use gridava::hex::shape::HexShape;
+    ///
+    /// // A line only has 3 distinct orientations.
+    /// assert_eq!(HexShape::make_line(3, 0, false, |_| 1).orientations(true).len(), 3);
+    /// ```
+    pub fn orientations(&self, reflections: bool) -> Vec<Transform<Axial>> {
+        let mut seen = BTreeSet::new();
+
+        orientations(reflections)
+            .filter(|orientation| seen.insert(normalize(self.oriented_coords(*orientation)).0))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::coordinate::Axes;
+
+    // A shape with no symmetry, a line with a tile off one side.
+    fn chiral() -> HexShape<i32> {
+        let mut shape = HexShape::make_line(2, 0, true, |_| 1);
+        shape.get_hexes_mut()[[0, 1]] = Some(2);
+        shape
+    }
+
+    #[test]
+    fn canonical() {
+        let shape = chiral();
+        let canonical = shape.canonical(false);
+        assert_eq!(canonical.transform, Transform::default());
+
+        // Every placement of the shape has the same canonical form.
+        for rot in 0..6 {
+            let mut moved = shape.clone();
+            moved.rotate(None, rot);
+            moved.translate(axial!(rot - 3, 2 * rot));
+            assert_eq!(moved.canonical(false), canonical);
+            assert_eq!(moved.canonical_key(false), shape.canonical_key(false));
+            assert_eq!(moved.canonical_hash(false), shape.canonical_hash(false));
+
+            // Data is carried along with the tiles.
+            assert_eq!(
+                moved
+                    .canonical(false)
+                    .iter()
+                    .filter(|(_, tile)| **tile == 2)
+                    .count(),
+                1
+            );
+        }
+
+        // Mirror images only match with reflections.
+        let mut mirrored = shape.clone();
+        mirrored.mirror(Axes::R);
+        assert_ne!(mirrored.canonical_key(false), shape.canonical_key(false));
+        assert_eq!(mirrored.canonical_key(true), shape.canonical_key(true));
+        assert_eq!(mirrored.canonical_hash(true), shape.canonical_hash(true));
+
+        // The transform takes the tiles to the canonical form.
+        let transform = mirrored.canonical_transform(true);
+        let mut coords: Vec<Axial> = mirrored
+            .iter()
+            .map(|(coord, _)| coord.apply_transform(transform))
+            .collect();
+        coords.sort();
+        assert_eq!(coords, mirrored.canonical_key(true));
+
+        // Different shapes have different forms.
+        assert_ne!(
+            HexShape::make_line(2, 0, false, |_| 1).canonical_hash(true),
+            HexShape::make_triangle(1, 0, false, |_| 1).canonical_hash(true)
+        );
+
+        let empty = HexShape::<i32>::new(None, None);
+        assert_eq!(empty.canonical(true).area(), 0);
+        assert!(empty.canonical_key(true).is_empty());
+    }
+
+    #[test]
+    fn canonical_hash() {
+        // The hash is fixed so it can be stored.
+        assert_eq!(
+            HexShape::make_line(0, 0, false, |_| 1).canonical_hash(true),
+            0xa8c7f832281a39c5
+        );
+    }
+
+    #[test]
+    fn symmetries() {
+        assert_eq!(chiral().symmetries(true), vec![Transform::default()]);
+        assert_eq!(
+            HexShape::make_hexagon(2, 0, false, |_| 1)
+                .symmetries(true)
+                .len(),
+            12
+        );
+        assert_eq!(
+            HexShape::make_hexagon(2, 0, false, |_| 1)
+                .symmetries(false)
+                .len(),
+            6
+        );
+        assert_eq!(
+            HexShape::make_triangle(2, 0, false, |_| 1)
+                .symmetries(true)
+                .len(),
+            6
+        );
+        assert_eq!(
+            HexShape::make_triangle(2, 0, false, |_| 1)
+                .symmetries(false)
+                .len(),
+            3
+        );
+        assert_eq!(
+            HexShape::make_rhombus(2, 0, false, |_| 1)
+                .symmetries(true)
+                .len(),
+            4
+        );
+        assert_eq!(
+            HexShape::make_line(2, 0, false, |_| 1)
+                .symmetries(true)
+                .len(),
+            4
+        );
+
+        // Every symmetry maps the tiles onto themselves.
+        let mut shape = HexShape::make_triangle(3, 1, false, |_| 1);
+        shape.translate(axial!(4, -7));
+        let mut coords: Vec<Axial> = shape.iter().map(|(coord, _)| coord).collect();
+        coords.sort();
+        for symmetry in shape.symmetries(true) {
+            let mut mapped: Vec<Axial> = shape
+                .iter()
+                .map(|(coord, _)| coord.apply_transform(symmetry))
+                .collect();
+            mapped.sort();
+            assert_eq!(mapped, coords);
+        }
+    }
+
+    #[test]
+    fn orientations() {
+        assert_eq!(chiral().orientations(true).len(), 12);
+        assert_eq!(chiral().orientations(false).len(), 6);
+        assert_eq!(
+            HexShape::make_hexagon(1, 0, false, |_| 1)
+                .orientations(true)
+                .len(),
+            1
+        );
+        assert_eq!(
+            HexShape::make_triangle(2, 0, false, |_| 1)
+                .orientations(true)
+                .len(),
+            2
+        );
+        assert_eq!(
+            HexShape::make_rhombus(2, 0, false, |_| 1)
+                .orientations(true)
+                .len(),
+            3
+        );
+
+        // The group size times the distinct orientations is every orientation.
+        let shape = HexShape::make_line(2, 0, false, |_| 1);
+        assert_eq!(
+            shape.symmetries(true).len() * shape.orientations(true).len(),
+            12
+        );
+    }
+}
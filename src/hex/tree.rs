@@ -0,0 +1,445 @@
+//! Shapes nested inside other shapes.
+//!
+//! A [`ShapeTree`] holds shapes in a hierarchy, like turrets on a hull or rooms in a house. The transform of
+//! each shape is relative to its parent, so moving or rotating a parent carries all of its children along.
+
+use crate::lib::*;
+
+use crate::{core::collection::Collection, core::transform::Transform};
+
+use super::{coordinate::Axial, orientable::Orientable, shape::HexShape, storage::ShapeStorage};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A handle to a shape in a [`ShapeTree`].
+///
+/// Handles are never reused, a handle to a removed shape stays invalid.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
+pub struct NodeId(usize);
+
+/// A shape and its place in the tree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+struct Node<T: Clone, S> {
+    /// The shape, its transform is relative to the parent.
+    shape: HexShape<T, S>,
+    /// The shape this one is attached to, None for a root.
+    parent: Option<NodeId>,
+    /// Shapes attached to this one in the order they were attached.
+    children: Vec<NodeId>,
+}
+
+/// A hierarchy of shapes.
+///
+/// Each shape's [`HexShape::transform`] is relative to its parent, roots are relative to the parent space. The
+/// world transform of a shape is its own transform composed with all of its ancestors', see
+/// [`Transform::compose`].
+///
+/// Shapes are drawn depth first, roots and children in the order they were attached, so a child's tiles are
+/// drawn over its parent's.
+///
+/// ```
+/// use gridava::hex::coordinate::{Axial, axial};
+/// use gridava::hex::shape::HexShape;
+/// use gridava::hex::tree::ShapeTree;
+///
+/// let mut ship = ShapeTree::new();
+/// let hull = ship.insert_root(HexShape::make_line(4, 0, false, |_| 'h'));
+///
+/// let mut turret = HexShape::make_line(0, 0, false, |_| 't');
+/// turret.translate(axial!(3, 0));
+/// ship.insert_child(hull, turret);
+///
+/// // Turn the ship, the turret turns with it.
+/// ship.shape_mut(hull).unwrap().rotate(None, 1);
+/// assert_eq!(ship.iter().find(|(_, tile)| **tile == 't').unwrap().0, axial!(0, 3));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ShapeTree<T: Clone, S = Array2<Option<T>>> {
+    /// Every node ever inserted, removed nodes are left as None so handles stay valid.
+    nodes: Vec<Option<Node<T, S>>>,
+    /// Shapes with no parent in the order they were attached.
+    roots: Vec<NodeId>,
+}
+
+impl<T: Clone, S> Default for ShapeTree<T, S> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            roots: vec![],
+        }
+    }
+}
+
+impl<T: Clone, S: ShapeStorage<T>> ShapeTree<T, S> {
+    /// Create an empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn node(&self, id: NodeId) -> Option<&Node<T, S>> {
+        self.nodes.get(id.0).and_then(|node| node.as_ref())
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node<T, S>> {
+        self.nodes.get_mut(id.0).and_then(|node| node.as_mut())
+    }
+
+    fn push(&mut self, shape: HexShape<T, S>, parent: Option<NodeId>) -> NodeId {
+        self.nodes.push(Some(Node {
+            shape,
+            parent,
+            children: vec![],
+        }));
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Add a shape with no parent, its transform is relative to the parent space.
+    pub fn insert_root(&mut self, shape: HexShape<T, S>) -> NodeId {
+        let id = self.push(shape, None);
+        self.roots.push(id);
+        id
+    }
+
+    /// Attach a shape to another, its transform is relative to the parent's.
+    ///
+    /// Returns None if the parent is not in the tree.
+    pub fn insert_child(&mut self, parent: NodeId, shape: HexShape<T, S>) -> Option<NodeId> {
+        self.node(parent)?;
+
+        let id = self.push(shape, Some(parent));
+        self.node_mut(parent)?.children.push(id);
+        Some(id)
+    }
+
+    /// Remove a shape along with everything attached to it.
+    ///
+    /// Returns the removed shape, None if it is not in the tree.
+    pub fn remove(&mut self, id: NodeId) -> Option<HexShape<T, S>> {
+        let node = self.nodes.get_mut(id.0)?.take()?;
+        self.detach(id, node.parent);
+
+        let mut stack = node.children;
+        while let Some(child) = stack.pop() {
+            if let Some(removed) = self.nodes[child.0].take() {
+                stack.extend(removed.children);
+            }
+        }
+
+        Some(node.shape)
+    }
+
+    // Remove a shape from the children of its parent, or from the roots.
+    fn detach(&mut self, id: NodeId, parent: Option<NodeId>) {
+        let siblings = match parent {
+            Some(parent) => match self.node_mut(parent) {
+                Some(node) => &mut node.children,
+                None => return,
+            },
+            None => &mut self.roots,
+        };
+        siblings.retain(|sibling| *sibling != id);
+    }
+
+    /// Move a shape and everything attached to it to another parent, None makes it a root.
+    ///
+    /// The shape's transform is kept, so it is now relative to the new parent. Returns false and leaves the
+    /// tree as it is if either shape is not in the tree or the new parent is attached to the shape.
+    pub fn set_parent(&mut self, id: NodeId, parent: Option<NodeId>) -> bool {
+        let Some(old) = self.node(id).map(|node| node.parent) else {
+            return false;
+        };
+
+        // Walk up from the new parent, meeting the shape would make a loop.
+        let mut ancestor = parent;
+        while let Some(current) = ancestor {
+            match self.node(current) {
+                Some(node) if current != id => ancestor = node.parent,
+                _ => return false,
+            }
+        }
+
+        self.detach(id, old);
+        match parent {
+            Some(parent) => self.node_mut(parent).unwrap().children.push(id),
+            None => self.roots.push(id),
+        }
+        self.node_mut(id).unwrap().parent = parent;
+        true
+    }
+
+    /// Get a shape in the tree.
+    pub fn shape(&self, id: NodeId) -> Option<&HexShape<T, S>> {
+        self.node(id).map(|node| &node.shape)
+    }
+
+    /// Get a shape in the tree mutably, changing its transform moves everything attached to it.
+    pub fn shape_mut(&mut self, id: NodeId) -> Option<&mut HexShape<T, S>> {
+        self.node_mut(id).map(|node| &mut node.shape)
+    }
+
+    /// Get the shape a shape is attached to, None for a root or a shape not in the tree.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id).and_then(|node| node.parent)
+    }
+
+    /// Get the shapes attached to a shape in the order they were attached.
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        self.node(id).map_or(&[], |node| &node.children)
+    }
+
+    /// Get the shapes with no parent in the order they were attached.
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+
+    /// Compute the transform of a shape in the parent space of the tree.
+    ///
+    /// None if the shape is not in the tree. See [`Transform::compose`].
+    pub fn world_transform(&self, id: NodeId) -> Option<Transform<Axial>> {
+        let mut node = self.node(id)?;
+        let mut transform = node.shape.transform;
+        while let Some(parent) = node.parent {
+            node = self.node(parent)?;
+            transform = node.shape.transform.compose(transform);
+        }
+        Some(transform)
+    }
+
+    /// Copy a shape with its transform set to its world transform.
+    ///
+    /// The copy's tiles are where the tree places them, see [`Self::world_transform`]. Useful to run any shape
+    /// method in the parent space, like [`HexShape::collisions`] between shapes in different trees.
+    pub fn world_shape(&self, id: NodeId) -> Option<HexShape<T, S>> {
+        let mut shape = self.shape(id)?.clone();
+        shape.transform = self.world_transform(id)?;
+        Some(shape)
+    }
+
+    /// List every shape depth first with its world transform.
+    ///
+    /// Shapes are listed in the order they are drawn, each shape before the shapes attached to it.
+    pub fn nodes(&self) -> Vec<(NodeId, Transform<Axial>)> {
+        let mut ret = vec![];
+        let mut stack: Vec<(NodeId, Transform<Axial>)> = self
+            .roots
+            .iter()
+            .rev()
+            .map(|root| (*root, Transform::default()))
+            .collect();
+
+        while let Some((id, parent)) = stack.pop() {
+            let node = self.node(id).unwrap();
+            let transform = parent.compose(node.shape.transform);
+            ret.push((id, transform));
+            stack.extend(node.children.iter().rev().map(|child| (*child, transform)));
+        }
+
+        ret
+    }
+
+    /// Iterate over the tiles of every shape in the parent space of the tree.
+    ///
+    /// Shapes are visited in the order of [`Self::nodes`], so a coordinate covered by more than one shape is
+    /// yielded for each of them.
+    pub fn iter(&self) -> impl Iterator<Item = (Axial, &T)> {
+        self.nodes().into_iter().flat_map(move |(id, transform)| {
+            self.node(id)
+                .unwrap()
+                .shape
+                .iter_local()
+                .map(move |(coord, tile)| (coord.apply_transform(transform), tile))
+        })
+    }
+
+    /// Iterate over the tiles of every shape in the parent space of the tree, oriented by their world
+    /// transforms.
+    ///
+    /// See [`Self::iter`] and [`HexShape::iter_oriented`].
+    pub fn iter_oriented(&self) -> impl Iterator<Item = (Axial, T)> + '_
+    where
+        T: Orientable,
+    {
+        self.nodes().into_iter().flat_map(move |(id, transform)| {
+            self.node(id)
+                .unwrap()
+                .shape
+                .iter_local()
+                .map(move |(coord, tile)| {
+                    (
+                        coord.apply_transform(transform),
+                        tile.apply_transform(transform),
+                    )
+                })
+        })
+    }
+
+    /// Flatten the tree into a single shape in the parent space.
+    ///
+    /// Where shapes overlap the last one drawn is kept, see [`Self::nodes`]. The data is kept as it is
+    /// stored, see [`Self::flatten_oriented`].
+    pub fn flatten(&self) -> HexShape<T, S> {
+        HexShape::from_tiles(self.iter().map(|(coord, tile)| (coord, tile.clone())))
+    }
+
+    /// Flatten the tree into a single shape, orienting the tiles by each shape's world transform.
+    ///
+    /// See [`Self::flatten`] and [`Orientable`].
+    pub fn flatten_oriented(&self) -> HexShape<T, S>
+    where
+        T: Orientable,
+    {
+        HexShape::from_tiles(self.iter_oriented())
+    }
+
+    /// Apply every shape in the tree to a collection.
+    ///
+    /// Shapes are applied in the order of [`Self::nodes`], so children are set over their parents.
+    pub fn apply_shape<COL: Collection<Axial, T>>(&self, col: &mut COL) {
+        self.iter().for_each(|(coord, value)| {
+            col.set(coord, value.clone());
+        });
+    }
+
+    /// Apply every shape in the tree to a collection, orienting the tiles by each shape's world transform.
+    ///
+    /// See [`Self::apply_shape`].
+    pub fn apply_oriented<COL: Collection<Axial, T>>(&self, col: &mut COL)
+    where
+        T: Orientable,
+    {
+        self.iter_oriented().for_each(|(coord, value)| {
+            col.set(coord, value);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::axial;
+
+    struct MockCollection {
+        tiles: HashMap<Axial, char>,
+    }
+
+    impl Collection<Axial, char> for MockCollection {
+        fn set(&mut self, coord: Axial, data: char) {
+            self.tiles.insert(coord, data);
+        }
+    }
+
+    // A hull along q with a turret on its last tile and a gun in front of the turret.
+    fn ship() -> (ShapeTree<char>, [NodeId; 3]) {
+        let mut tree = ShapeTree::new();
+        let hull = tree.insert_root(HexShape::make_line(3, 0, false, |_| 'h'));
+
+        let mut turret = HexShape::make_line(0, 0, false, |_| 't');
+        turret.translate(axial!(3, 0));
+        let turret = tree.insert_child(hull, turret).unwrap();
+
+        let mut gun = HexShape::make_line(0, 0, false, |_| 'g');
+        gun.translate(axial!(1, 0));
+        let gun = tree.insert_child(turret, gun).unwrap();
+
+        (tree, [hull, turret, gun])
+    }
+
+    #[test]
+    fn world_transform() {
+        let (mut tree, [hull, turret, gun]) = ship();
+        assert_eq!(tree.world_transform(gun).unwrap().translation, axial!(4, 0));
+
+        // Moving the hull carries everything along.
+        tree.shape_mut(hull).unwrap().rotate(None, 2);
+        tree.shape_mut(hull).unwrap().translate(axial!(5, 5));
+        assert_eq!(tree.world_transform(gun).unwrap().translation, axial!(1, 9));
+        assert_eq!(tree.world_transform(gun).unwrap().rotation, 2);
+
+        // Turning the turret only moves the gun.
+        tree.shape_mut(turret).unwrap().rotate(None, 1);
+        assert_eq!(
+            tree.world_transform(turret).unwrap().translation,
+            axial!(2, 8)
+        );
+        assert_eq!(tree.world_transform(gun).unwrap().translation, axial!(1, 8));
+        assert_eq!(
+            tree.world_shape(gun).unwrap().iter().next().unwrap().0,
+            axial!(1, 8)
+        );
+
+        let nodes: Vec<NodeId> = tree.nodes().into_iter().map(|(id, _)| id).collect();
+        assert_eq!(nodes, vec![hull, turret, gun]);
+    }
+
+    #[test]
+    fn flatten() {
+        let (tree, _) = ship();
+
+        // The turret is drawn over the end of the hull.
+        let flat = tree.flatten();
+        assert_eq!(flat.area(), 5);
+        assert_eq!(flat.get(axial!(3, 0)), Some(&'t'));
+        assert_eq!(flat.get(axial!(4, 0)), Some(&'g'));
+        assert_eq!(tree.iter().count(), 6);
+
+        let mut col = MockCollection {
+            tiles: HashMap::new(),
+        };
+        tree.apply_shape(&mut col);
+        assert_eq!(col.tiles.len(), 5);
+        assert_eq!(col.tiles[&axial!(3, 0)], 't');
+    }
+
+    #[test]
+    fn flatten_oriented() {
+        use crate::hex::coordinate::HexDirection;
+
+        // An arrow on a turned parent points along the parent's turn as well as its own.
+        let mut tree = ShapeTree::new();
+        let mut base = HexShape::make_line(0, 0, false, |_| HexDirection::Front);
+        base.rotate(None, 1);
+        let base = tree.insert_root(base);
+        let mut arrow = HexShape::make_line(0, 0, false, |_| HexDirection::Front);
+        arrow.rotate(None, 1);
+        arrow.translate(axial!(2, 0));
+        tree.insert_child(base, arrow).unwrap();
+
+        let flat = tree.flatten_oriented();
+        assert_eq!(flat.get(axial!(0, 0)), Some(&HexDirection::FrontRight));
+        assert_eq!(flat.get(axial!(0, 2)), Some(&HexDirection::BackRight));
+        assert_eq!(tree.flatten().get(axial!(0, 2)), Some(&HexDirection::Front));
+    }
+
+    #[test]
+    fn structure() {
+        let (mut tree, [hull, turret, gun]) = ship();
+        assert_eq!(tree.roots(), &[hull]);
+        assert_eq!(tree.children(turret), &[gun]);
+        assert_eq!(tree.parent(gun), Some(turret));
+
+        // A shape can not be attached below itself.
+        assert!(!tree.set_parent(hull, Some(gun)));
+        assert!(!tree.set_parent(turret, Some(turret)));
+
+        // Detaching the turret leaves it where its transform alone places it.
+        assert!(tree.set_parent(turret, None));
+        assert_eq!(tree.roots(), &[hull, turret]);
+        assert!(tree.children(hull).is_empty());
+        assert!(tree.set_parent(turret, Some(hull)));
+
+        // Removing the turret removes the gun too.
+        let removed = tree.remove(turret).unwrap();
+        assert_eq!(removed.get(axial!(3, 0)), Some(&'t'));
+        assert_eq!(tree.shape(gun), None);
+        assert!(tree.children(hull).is_empty());
+        assert_eq!(tree.insert_child(gun, HexShape::new(None, None)), None);
+        assert_eq!(tree.remove(turret), None);
+        assert_eq!(tree.flatten().area(), 4);
+    }
+}
@@ -1,9 +1,11 @@
 //! Handles vertices in a hexagonal grid.
 
-use crate::edge;
+use crate::lib::*;
+
+use crate::{core::transform::Transform, edge};
 
 use super::{
-    coordinate::{axial, Axial},
+    coordinate::{axial, Axes, Axial},
     edge::{Edge, EdgeDirection},
 };
 
@@ -17,7 +19,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// see [`Vertex`]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
 pub enum VertexSpin {
     /// On top of the hex
     Up,
@@ -83,7 +85,7 @@ impl From<VertexDirection> for i32 {
 ///
 /// See [`vertex`] for helper macro to instantiate these structs.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(PartialEq, Eq, Copy, Clone, Hash, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
 pub struct Vertex {
     /// q (x) coordinate of the vertex
     pub q: i32,
@@ -136,6 +138,81 @@ impl From<(Axial, VertexSpin)> for Vertex {
 }
 
 impl Vertex {
+    /// Rotate a vertex.
+    ///
+    /// `center` Optionally can specify a hex to rotate about. None will rotate about (0, 0).
+    ///
+    /// `rot_dir`: positive denotes CW, negative CCW, magnitude denotes how many 60 degree rotations.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::vertex::{Vertex, VertexSpin, vertex};
+    ///
+    /// // vert will be the top right vertex of (0, 0), (1, -1, Down)
+    /// let vert = vertex!(0, 0, VertexSpin::Up).rotate(None, 1);
+    /// ```
+    pub fn rotate(&self, center: Option<Axial>, rot_dir: i32) -> Self {
+        // The vertex is the `Up` or `Down` corner of its own hex, rotate that hex and step the corner around.
+        let dir: i32 = match self.spin {
+            VertexSpin::Up => VertexDirection::Up.into(),
+            VertexSpin::Down => VertexDirection::Down.into(),
+        };
+        let hex = axial!(self.q, self.r).rotate(center, rot_dir);
+        let offset = Vertex::from(VertexDirection::from(dir + rot_dir));
+
+        vertex!(hex.q + offset.q, hex.r + offset.r, offset.spin)
+    }
+
+    /// Reflect a vertex.
+    ///
+    /// `center` Optionally can specify a hex to reflect about. None will reflect about (0, 0).
+    ///
+    /// See [`Axial::reflect`].
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::Axes;
+    /// use gridava::hex::vertex::{Vertex, VertexSpin, vertex};
+    ///
+    /// // vert will be the bottom left vertex of (0, 0), (-1, 1, Up)
+    /// let vert = vertex!(0, 0, VertexSpin::Up).reflect(None, Axes::S);
+    /// ```
+    pub fn reflect(&self, center: Option<Axial>, axes: Axes) -> Self {
+        let [a, b, c] = self.adjacent_hexes().map(|hex| hex.reflect(center, axes));
+        // The three hexes around a vertex still meet at a vertex under reflection.
+        a.vertices()
+            .into_iter()
+            .find(|vert| b.vertices().contains(vert) && c.vertices().contains(vert))
+            .unwrap()
+    }
+
+    /// Applies a transform matrix to this vertex.
+    ///
+    /// Scale has no meaning with a point so we do not scale here.
+    ///
+    /// The order of applications is reflection across the q axis, rotation then translation.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::coordinate::{Axial, axial};
+    /// use gridava::hex::vertex::{Vertex, VertexSpin, vertex};
+    /// use gridava::core::transform::{Transform, transform, Vector2D, vector2d};
+    ///
+    /// let new_vert = vertex!(2, 5, VertexSpin::Up).apply_transform(transform!(axial!(1, 1), 4));
+    /// ```
+    pub fn apply_transform(&self, transform: Transform<Axial>) -> Self {
+        let vert = match transform.reflected {
+            true => self.reflect(None, Axes::Q),
+            false => *self,
+        };
+        let vert = vert.rotate(None, transform.rotation);
+        vertex!(
+            vert.q + transform.translation.q,
+            vert.r + transform.translation.r,
+            vert.spin
+        )
+    }
+
     /// Get all 3 adjacent hexes to this vertex.
     ///
     /// # Example
@@ -313,11 +390,139 @@ impl Vertex {
         // Calculate final distance
         2 * dist + base_adjustment
     }
+
+    /// Get the edge connecting two adjacent vertices.
+    ///
+    /// Returns None if the vertices are not adjacent.
+    ///
+    /// ```
+    /// use gridava::hex::vertex::{Vertex, VertexSpin, vertex};
+    ///
+    /// let edge = vertex!(0, 0, VertexSpin::Up).edge_to(vertex!(1, -1, VertexSpin::Down));
+    /// ```
+    pub fn edge_to(&self, b: Self) -> Option<Edge> {
+        if !self.adjacent_vertices().contains(&b) {
+            return None;
+        }
+
+        let b_edges = b.adjacent_edges();
+        self.adjacent_edges()
+            .into_iter()
+            .find(|edge| b_edges.contains(edge))
+    }
+
+    // Vertices are the triangles of the lattice formed by hex centers, so the number of edges between
+    // two vertices is the L1 distance of their triangle coordinates.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn triangle_coord(&self) -> (i32, i32, i32) {
+        let s = -self.q - self.r;
+        match self.spin {
+            VertexSpin::Up => (self.q + 1, self.r, s + 1),
+            VertexSpin::Down => (self.q, self.r + 1, s),
+        }
+    }
+
+    // Number of edges on a shortest path between two vertices.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn steps_to(&self, b: Self) -> i32 {
+        let (aq, ar, as_) = self.triangle_coord();
+        let (bq, br, bs) = b.triangle_coord();
+        (aq - bq).abs() + (ar - br).abs() + (as_ - bs).abs()
+    }
+
+    // Position of the vertex in a uniformly scaled world space, good for comparing lengths and areas.
+    //
+    // A hex center maps to (2q + r, 3r) and a vertex is the sum of its three hex centers. Using integers
+    // keeps lines deterministic.
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) fn scaled_position(&self) -> (i64, i64) {
+        self.adjacent_hexes().iter().fold((0, 0), |(x, y), hex| {
+            let (hx, hy) = Self::scaled_center(*hex);
+            (x + hx, y + hy)
+        })
+    }
+
+    // Position of a hex center in the same space as [`Vertex::scaled_position`].
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub(crate) fn scaled_center(hex: Axial) -> (i64, i64) {
+        (2 * hex.q as i64 + hex.r as i64, 3 * hex.r as i64)
+    }
+
+    /// Calculate the vertices that form a line between two vertices.
+    ///
+    /// The line walks a shortest path between the two vertices, at each step choosing the vertex closest to
+    /// the straight segment between them. Consecutive vertices are always adjacent and the result is the
+    /// same in either direction, only reversed.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::vertex::{Vertex, VertexSpin, vertex};
+    ///
+    /// // verts will start with (0, 0, Up) and end with (2, -1, Up)
+    /// let verts = vertex!(0, 0, VertexSpin::Up).line(vertex!(2, -1, VertexSpin::Up));
+    /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn line(&self, b: Self) -> Vec<Self> {
+        // Always walk from the lesser vertex so the line is the same in both directions.
+        if b < *self {
+            let mut ret = b.line(*self);
+            ret.reverse();
+            return ret;
+        }
+
+        let (ax, ay) = self.scaled_position();
+        let (bx, by) = b.scaled_position();
+
+        // Proportional to the distance of a vertex from the segment.
+        let off_line = |vert: &Self| {
+            let (x, y) = vert.scaled_position();
+            ((bx - ax) * (y - ay) - (by - ay) * (x - ax)).abs()
+        };
+
+        let mut ret = vec![*self];
+        let mut current = *self;
+
+        while current != b {
+            let steps = current.steps_to(b);
+            current = current
+                .adjacent_vertices()
+                .into_iter()
+                .filter(|vert| vert.steps_to(b) < steps)
+                .min_by_key(|vert| (off_line(vert), *vert))
+                .expect("a vertex always has a neighbor closer to the target");
+            ret.push(current);
+        }
+
+        ret
+    }
+
+    /// Calculate the edges that form a line between two vertices.
+    ///
+    /// See [`Vertex::line`], the edges connect each consecutive vertex of that line.
+    ///
+    /// # Example
+    /// ```
+    /// use gridava::hex::vertex::{Vertex, VertexSpin, vertex};
+    ///
+    /// // edges will contain the two edges between (0, 0, Up) and (1, -1, Up)
+    /// let edges = vertex!(0, 0, VertexSpin::Up).edge_line(vertex!(1, -1, VertexSpin::Up));
+    /// ```
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn edge_line(&self, b: Self) -> Vec<Edge> {
+        self.line(b)
+            .windows(2)
+            .filter_map(|pair| pair[0].edge_to(pair[1]))
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        core::transform::{vector2d, Vector2D},
+        transform,
+    };
 
     #[test]
     fn from_axial() {
@@ -327,6 +532,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rotate() {
+        // Every corner of a hex steps to the next corner CW.
+        let corners = axial!(0, 0).vertices();
+        for i in 0..6 {
+            assert_eq!(corners[i].rotate(None, 1), corners[(i + 1) % 6]);
+            assert_eq!(corners[i].rotate(None, -1), corners[(i + 5) % 6]);
+            assert_eq!(corners[i].rotate(None, 6), corners[i]);
+        }
+
+        // The hexes around a vertex rotate with it.
+        let vert = vertex!(2, -1, VertexSpin::Down);
+        for rot in -6..=6 {
+            let mut expected = vert
+                .adjacent_hexes()
+                .map(|hex| hex.rotate(Some(axial!(1, 1)), rot));
+            let mut actual = vert.rotate(Some(axial!(1, 1)), rot).adjacent_hexes();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn apply_transform() {
+        let transform = transform!(axial!(1, 1), 1);
+        assert_eq!(
+            vertex!(0, 0, VertexSpin::Up).apply_transform(transform),
+            vertex!(2, 0, VertexSpin::Down)
+        );
+        assert_eq!(
+            vertex!(0, 0, VertexSpin::Down).apply_transform(transform),
+            vertex!(0, 2, VertexSpin::Up)
+        );
+    }
+
     #[test]
     fn adjacent_hexes() {
         assert_eq!(
@@ -440,6 +681,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn edge_to() {
+        let vert = vertex!(0, 0, VertexSpin::Up);
+        for (adj, edge) in vert.adjacent_vertices().iter().zip([
+            edge!(0, 0, EdgeDirection::NorthEast),
+            edge!(0, 0, EdgeDirection::NorthWest),
+            edge!(1, -1, EdgeDirection::West),
+        ]) {
+            assert_eq!(vert.edge_to(*adj), Some(edge));
+            assert_eq!(adj.edge_to(vert), Some(edge));
+        }
+
+        assert_eq!(vert.edge_to(vert), None);
+        assert_eq!(vert.edge_to(vertex!(0, 0, VertexSpin::Down)), None);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn all_vertices(range: i32) -> Vec<Vertex> {
+        axial!(0, 0)
+            .range(range)
+            .into_iter()
+            .flat_map(|hex| [(hex, VertexSpin::Up).into(), (hex, VertexSpin::Down).into()])
+            .collect()
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn steps_to() {
+        use std::collections::{HashMap, VecDeque};
+
+        // Breadth first search from a vertex must agree with the closed form.
+        let start = vertex!(0, 0, VertexSpin::Down);
+        let mut steps = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(vert) = queue.pop_front() {
+            let dist = steps[&vert];
+            if dist == 8 {
+                continue;
+            }
+            for adj in vert.adjacent_vertices() {
+                steps.entry(adj).or_insert_with(|| {
+                    queue.push_back(adj);
+                    dist + 1
+                });
+            }
+        }
+
+        for (vert, dist) in steps {
+            assert_eq!(start.steps_to(vert), dist);
+            assert_eq!(vert.steps_to(start), dist);
+        }
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn line() {
+        let a = vertex!(0, 0, VertexSpin::Up);
+        assert_eq!(a.line(a), vec![a]);
+
+        // Straight along the top of a row of hexes.
+        assert_eq!(
+            a.line(vertex!(2, 0, VertexSpin::Up)),
+            vec![
+                vertex!(0, 0, VertexSpin::Up),
+                vertex!(1, -1, VertexSpin::Down),
+                vertex!(1, 0, VertexSpin::Up),
+                vertex!(2, -1, VertexSpin::Down),
+                vertex!(2, 0, VertexSpin::Up),
+            ]
+        );
+
+        // Down the left side of (0, 0) then across its bottom.
+        assert_eq!(
+            vertex!(0, -1, VertexSpin::Down).line(vertex!(-1, 2, VertexSpin::Up)),
+            vec![
+                vertex!(0, -1, VertexSpin::Down),
+                vertex!(-1, 1, VertexSpin::Up),
+                vertex!(0, 0, VertexSpin::Down),
+                vertex!(-1, 2, VertexSpin::Up),
+            ]
+        );
+
+        let verts = all_vertices(2);
+        for a in &verts {
+            for b in &verts {
+                let line = a.line(*b);
+
+                // Shortest, connected and between the two vertices.
+                assert_eq!(line.len() as i32, a.steps_to(*b) + 1);
+                assert_eq!(line.first(), Some(a));
+                assert_eq!(line.last(), Some(b));
+                assert!(line
+                    .windows(2)
+                    .all(|pair| pair[0].adjacent_vertices().contains(&pair[1])));
+
+                // Symmetric.
+                let mut reverse = b.line(*a);
+                reverse.reverse();
+                assert_eq!(line, reverse);
+            }
+        }
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn edge_line() {
+        let a = vertex!(0, 0, VertexSpin::Up);
+        assert_eq!(a.edge_line(a), vec![]);
+        assert_eq!(
+            a.edge_line(vertex!(2, 0, VertexSpin::Up)),
+            vec![
+                edge!(0, 0, EdgeDirection::NorthEast),
+                edge!(1, 0, EdgeDirection::NorthWest),
+                edge!(1, 0, EdgeDirection::NorthEast),
+                edge!(2, 0, EdgeDirection::NorthWest),
+            ]
+        );
+
+        let verts = all_vertices(2);
+        for a in &verts {
+            for b in &verts {
+                let edges = a.edge_line(*b);
+                assert_eq!(edges.len() as i32, a.steps_to(*b));
+                assert!(edges
+                    .windows(2)
+                    .all(|pair| pair[0].adjacent_edges().contains(&pair[1])));
+            }
+        }
+    }
+
     #[test]
     fn from_i32() {
         for i in 0..=5 {
@@ -490,4 +861,40 @@ mod tests {
     fn default() {
         assert_eq!(Vertex::default(), vertex!(0, 0, VertexSpin::Up));
     }
+
+    #[test]
+    fn reflect() {
+        assert_eq!(
+            vertex!(0, 0, VertexSpin::Up).reflect(None, Axes::S),
+            vertex!(-1, 1, VertexSpin::Up)
+        );
+
+        // The reflected vertex is the corner of the reflected hexes.
+        for axes in [Axes::Q, Axes::R, Axes::S] {
+            for vert in all_vertices(2) {
+                let reflected = vert.reflect(Some(axial!(1, -1)), axes);
+                let mut hexes = reflected.adjacent_hexes();
+                hexes.sort();
+                let mut expected = vert
+                    .adjacent_hexes()
+                    .map(|hex| hex.reflect(Some(axial!(1, -1)), axes));
+                expected.sort();
+                assert_eq!(hexes, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn apply_transform_reflected() {
+        let transform = transform!(axial!(1, 1), 1, vector2d!(1.0), true);
+        for vert in all_vertices(2) {
+            let mut hexes = vert.apply_transform(transform).adjacent_hexes();
+            hexes.sort();
+            let mut expected = vert
+                .adjacent_hexes()
+                .map(|hex| hex.apply_transform(transform));
+            expected.sort();
+            assert_eq!(hexes, expected);
+        }
+    }
 }
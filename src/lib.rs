@@ -1,6 +1,8 @@
 //! A library for working with grids of different shapes.
 
 #![forbid(unsafe_code)]
+// Option::is_none_or needs Rust 1.82, map_or(true, ..) keeps older compilers working.
+#![allow(clippy::unnecessary_map_or)]
 #![no_std]
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -24,16 +26,27 @@ mod lib {
     pub use self::core::cmp::PartialEq;
     pub use self::core::f64;
     pub use self::core::fmt::{self, Display};
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub use self::core::marker::PhantomData;
     pub use self::core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
 
     #[cfg(all(feature = "alloc", not(feature = "std")))]
-    pub use alloc::vec::Vec;
+    pub use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+    #[cfg(feature = "std")]
+    pub use std::{boxed::Box, string::String, vec, vec::Vec};
+
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    pub use alloc::collections::{btree_map, BTreeMap, BTreeSet};
 
     #[cfg(feature = "std")]
-    pub use std::{vec, vec::Vec};
+    pub use std::collections::{btree_map, BTreeMap, BTreeSet};
 
     #[cfg(any(feature = "std", feature = "alloc"))]
-    pub use ndarray::{array, Array, Array2};
+    pub use ndarray::{Array, Array2};
+
+    #[cfg(all(test, any(feature = "std", feature = "alloc")))]
+    pub use ndarray::array;
 
     // Use libm when no_std
     #[cfg(not(feature = "std"))]